@@ -12,4 +12,50 @@ pub const REWARD_TYPE_OTHER: u8 = 2;
 pub const BADGE_ACTIVE: bool = true;
 pub const BADGE_INACTIVE: bool = false;
 pub const REWARD_ACTIVE: bool = true;
-pub const REWARD_INACTIVE: bool = false;
\ No newline at end of file
+pub const REWARD_INACTIVE: bool = false;
+
+// Maximum candidate accounts per get_user_badges / get_user_rewards call,
+// keeping the returned summary within the 1024 byte return data limit
+pub const MAX_QUERY_ACCOUNTS: usize = 20;
+
+// Badge revocation reason codes
+pub const REVOKE_REASON_OTHER: u8 = 0;
+pub const REVOKE_REASON_FRAUD: u8 = 1;
+pub const REVOKE_REASON_POLICY_VIOLATION: u8 = 2;
+pub const REVOKE_REASON_ISSUED_IN_ERROR: u8 = 3;
+pub const REVOKE_REASON_USER_REQUEST: u8 = 4;
+
+// Token metadata symbol for soulbound badge NFTs
+pub const BADGE_NFT_SYMBOL: &str = "BADGE";
+
+// Protocol fee cap, in basis points
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 10_000;
+
+// How long a badge that has been issued must stay deactivated before it can be closed
+pub const BADGE_CLOSE_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+// Current layout version of each account type, bumped whenever its fields change.
+// New fields are appended after the existing ones so migrate_account can zero-fill them.
+// Accounts on an older layout must go through migrate_account before use.
+pub const BADGE_VERSION: u8 = 6;
+pub const REWARD_VERSION: u8 = 6;
+pub const USER_BADGE_VERSION: u8 = 5;
+pub const USER_REWARD_VERSION: u8 = 3;
+pub const USER_POINTS_VERSION: u8 = 1;
+pub const REVOKED_BADGE_VERSION: u8 = 1;
+pub const CONFIG_VERSION: u8 = 1;
+pub const COLLECTION_VERSION: u8 = 1;
+pub const VESTING_ESCROW_VERSION: u8 = 1;
+pub const CLOSED_BADGE_VERSION: u8 = 1;
+
+// Maximum number of upgrade tiers above a badge's base level
+pub const MAX_BADGE_TIERS: usize = 5;
+
+// Maximum number of badges that must be held before a badge can be earned
+pub const MAX_BADGE_PREREQUISITES: usize = 5;
+
+// Maximum number of member badges in a collection
+pub const MAX_COLLECTION_BADGES: usize = 10;
+
+// Fixed-point scale of a badge's accumulated yield per held badge
+pub const YIELD_PRECISION: u128 = 1_000_000_000_000;
\ No newline at end of file
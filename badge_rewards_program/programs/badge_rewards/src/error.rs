@@ -44,4 +44,148 @@ pub enum BadgeRewardsError {
 	MissingNftMint,
 	#[msg("Invalid token mint")]
 	InvalidTokenMint,
+	#[msg("Invalid points amount")]
+	InvalidPointsAmount,
+	#[msg("Points balance overflow")]
+	PointsOverflow,
+	#[msg("Rent recipient does not match the account funder")]
+	InvalidRentRecipient,
+	#[msg("Badge has been revoked")]
+	BadgeRevoked,
+	#[msg("Invalid revocation reason code")]
+	InvalidReasonCode,
+	#[msg("Evidence URI too long")]
+	EvidenceUriTooLong,
+	#[msg("Too many accounts passed")]
+	TooManyAccounts,
+	#[msg("Invalid user badge account")]
+	InvalidUserBadge,
+	#[msg("Invalid user reward account")]
+	InvalidUserReward,
+	#[msg("NFT mint must match the reward and have 0 decimals and a supply of 1")]
+	InvalidNftMint,
+	#[msg("Missing reward vault accounts")]
+	MissingRewardVault,
+	#[msg("Invalid reward vault")]
+	InvalidRewardVault,
+	#[msg("Invalid token account")]
+	InvalidTokenAccount,
+	#[msg("Insufficient reward vault balance")]
+	InsufficientVaultBalance,
+	#[msg("Invalid token amount")]
+	InvalidAmount,
+	#[msg("Reward is already active")]
+	RewardAlreadyActive,
+	#[msg("Reward is not staked")]
+	RewardNotStaked,
+	#[msg("Reward is already staked")]
+	RewardAlreadyStaked,
+	#[msg("Minimum stake period has not elapsed")]
+	MinStakePeriodNotMet,
+	#[msg("Only the staker can unstake this reward")]
+	UnauthorizedUnstake,
+	#[msg("Invalid minimum stake period")]
+	InvalidStakePeriod,
+	#[msg("Reward is not available for claiming")]
+	RewardNotAvailable,
+	#[msg("Missing reward escrow accounts")]
+	MissingRewardEscrow,
+	#[msg("Transfer fee calculation overflow")]
+	TransferFeeOverflow,
+	#[msg("Missing badge NFT accounts")]
+	MissingBadgeMint,
+	#[msg("Badge NFT mint does not match the user badge")]
+	InvalidBadgeMint,
+	#[msg("Program is paused")]
+	ProgramPaused,
+	#[msg("Protocol fee must not exceed 10000 basis points")]
+	InvalidProtocolFee,
+	#[msg("Invalid admin")]
+	InvalidAdmin,
+	#[msg("Signer is not the proposed owner")]
+	NotPendingOwner,
+	#[msg("Badge has been issued and cannot be closed")]
+	BadgeHasHolders,
+	#[msg("Reward vault still holds tokens")]
+	VaultNotEmpty,
+	#[msg("Account is on an old or unknown layout, migrate it first")]
+	UnsupportedAccountVersion,
+	#[msg("Account is already on the current layout")]
+	AccountAlreadyMigrated,
+	#[msg("Account data does not match any known layout")]
+	InvalidAccountLayout,
+	#[msg("Badge has expired")]
+	BadgeExpired,
+	#[msg("Validity period must be greater than zero")]
+	InvalidValidityPeriod,
+	#[msg("Badge does not expire")]
+	BadgeDoesNotExpire,
+	#[msg("Too many badge tiers")]
+	TooManyTiers,
+	#[msg("Each tier must require more points than the level below it")]
+	InvalidTierPoints,
+	#[msg("User does not qualify for a higher tier")]
+	NoTierUpgrade,
+	#[msg("Badge tier is below the reward's minimum tier")]
+	InsufficientTier,
+	#[msg("Too many prerequisite badges")]
+	TooManyPrerequisites,
+	#[msg("A badge cannot require itself or list the same prerequisite twice")]
+	InvalidPrerequisite,
+	#[msg("User does not hold a required prerequisite badge")]
+	MissingPrerequisite,
+	#[msg("Too many badges in the collection")]
+	TooManyCollectionBadges,
+	#[msg("A collection needs distinct member badges other than its meta-badge")]
+	InvalidCollection,
+	#[msg("User does not hold every badge in the collection")]
+	CollectionIncomplete,
+	#[msg("Collections cannot award a meta-badge that mints an NFT")]
+	MetaBadgeMintsNft,
+	#[msg("Reward has been claimed the maximum number of times")]
+	RewardClaimLimitReached,
+	#[msg("Max claims must be greater than zero and not below the claims already made")]
+	InvalidMaxClaims,
+	#[msg("Claim window must open before it closes")]
+	InvalidClaimWindow,
+	#[msg("Reward claim window has not opened yet")]
+	ClaimWindowNotOpen,
+	#[msg("Reward claim window has closed")]
+	ClaimWindowClosed,
+	#[msg("Reward claim window has not closed yet")]
+	ClaimWindowNotClosed,
+	#[msg("Vesting needs a token reward with a nonzero duration and a cliff within it")]
+	InvalidVestingSchedule,
+	#[msg("Vesting escrow is required for this reward")]
+	MissingVestingEscrow,
+	#[msg("Vesting escrow given for a reward that does not vest")]
+	InvalidVestingEscrow,
+	#[msg("No vested tokens are available to withdraw")]
+	NothingVested,
+	#[msg("Badge has not been revoked since the reward was claimed")]
+	BadgeNotRevoked,
+	#[msg("No unvested tokens are left to reclaim")]
+	NothingToReclaim,
+	#[msg("Badge yield calculation overflowed")]
+	YieldOverflow,
+	#[msg("Badge yield mint cannot be changed once set")]
+	YieldMintMismatch,
+	#[msg("No yield has accrued since the last harvest")]
+	NothingToHarvest,
+	#[msg("User badge was not issued from this badge")]
+	UserBadgeMismatch,
+	#[msg("Reward has vesting escrows that have not fully vested")]
+	VestingInProgress,
+	#[msg("Missing badge yield accounts")]
+	MissingYieldAccounts,
+	#[msg("Badge yield vault still holds tokens")]
+	YieldVaultNotEmpty,
+	#[msg("Player no longer holds any of the claimed reward to lock")]
+	NothingToLock,
+	#[msg("Badge must be deactivated for the grace period before it can be closed")]
+	BadgeCloseGracePeriod,
+	#[msg("Reward's minimum tier is above the badge's highest tier")]
+	InvalidMinTier,
+	#[msg("Reward type and mints cannot change once the reward is funded or claimed")]
+	RewardTermsLocked,
 }
\ No newline at end of file
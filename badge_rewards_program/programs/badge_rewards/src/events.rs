@@ -0,0 +1,368 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct RewardStaked {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub staked_by: Pubkey,
+	pub min_stake_period: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardUnstaked {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub unstaked_by: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct PlayerRewardStaked {
+	pub owner: Pubkey,
+	pub reward_id: u64,
+	pub player: Pubkey,
+	pub min_stake_period: u64,
+	pub locked_amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct PlayerRewardUnstaked {
+	pub owner: Pubkey,
+	pub reward_id: u64,
+	pub player: Pubkey,
+	pub unlocked_amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeCreated {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub name: String,
+	pub required_points: u64,
+	pub max_earnings: u64,
+	pub is_active: bool,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeUpdated {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub required_points: u64,
+	pub max_earnings: u64,
+	pub is_active: bool,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeFrozen {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeStaked {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub total_issued: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeUnstaked {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeRevoked {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub reason_code: u8,
+	pub allow_reissue: bool,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardCreated {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub reward_type: u8,
+	pub reward_value: u64,
+	pub is_active: bool,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardUpdated {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub reward_type: u8,
+	pub reward_value: u64,
+	pub is_active: bool,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardClaimed {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub user: Pubkey,
+	pub reward_type: u8,
+	pub amount: u64,
+	pub total_claimed: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardFunded {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub amount: u64,
+	pub vault_balance: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardWithdrawn {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub amount: u64,
+	pub vault_balance: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct PointsAwarded {
+	pub owner: Pubkey,
+	pub user: Pubkey,
+	pub amount: u64,
+	pub balance: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct PointsDeducted {
+	pub owner: Pubkey,
+	pub user: Pubkey,
+	pub amount: u64,
+	pub balance: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigInitialized {
+	pub admin: Pubkey,
+	pub protocol_fee_bps: u16,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseSet {
+	pub admin: Pubkey,
+	pub paused: bool,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeeSet {
+	pub admin: Pubkey,
+	pub previous_fee_bps: u16,
+	pub protocol_fee_bps: u16,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferred {
+	pub previous_admin: Pubkey,
+	pub new_admin: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeOwnerProposed {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub authority: Pubkey,
+	pub pending_authority: Option<Pubkey>,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeOwnerTransferred {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub previous_authority: Pubkey,
+	pub new_authority: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardOwnerProposed {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub authority: Pubkey,
+	pub pending_authority: Option<Pubkey>,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardOwnerTransferred {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub previous_authority: Pubkey,
+	pub new_authority: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeClosed {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub holders: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardClosed {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub swept_amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+	pub account: Pubkey,
+	pub from_version: u8,
+	pub to_version: u8,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct UserBadgeBackfilled {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub badge: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeRenewed {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub expires_at: i64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeUpgraded {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub tier: u8,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionCreated {
+	pub owner: Pubkey,
+	pub collection_id: u64,
+	pub meta_badge_id: u64,
+	pub badge_ids: Vec<u64>,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct CollectionCompleted {
+	pub owner: Pubkey,
+	pub collection_id: u64,
+	pub meta_badge_id: u64,
+	pub user: Pubkey,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardSwept {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub reward_id: u64,
+	pub recipient: Pubkey,
+	pub amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedWithdrawn {
+	pub user: Pubkey,
+	pub reward: Pubkey,
+	pub amount: u64,
+	pub withdrawn_amount: u64,
+	pub total_amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct UnvestedReclaimed {
+	pub user: Pubkey,
+	pub reward: Pubkey,
+	pub amount: u64,
+	pub vested_amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeYieldSet {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub yield_mint: Pubkey,
+	pub emission_rate: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeYieldFunded {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub amount: u64,
+	pub vault_balance: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldHarvested {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub user: Pubkey,
+	pub amount: u64,
+	pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeYieldWithdrawn {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	pub amount: u64,
+	pub vault_balance: u64,
+	pub timestamp: i64,
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct AcceptBadgeOwner<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	pub new_authority: Signer<'info>,
+}
+
+/// Accept a pending ownership proposal for a badge
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[signer]` new_authority: [AccountInfo] The proposed owner taking over the badge
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to accept
+pub fn handler(
+	ctx: Context<AcceptBadgeOwner>,
+	badge_id: u64,
+) -> Result<()> {
+    // Validate that the badge exists and the signer is the proposed owner
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.badge.pending_authority == Some(ctx.accounts.new_authority.key()), BadgeRewardsError::NotPendingOwner);
+    
+    // Hand over the badge; the PDA stays keyed by the original owner
+    let badge = &mut ctx.accounts.badge;
+    let previous_authority = badge.authority;
+    badge.authority = ctx.accounts.new_authority.key();
+    badge.pending_authority = None;
+    
+    emit_cpi!(BadgeOwnerTransferred {
+        owner: badge.owner,
+        badge_id,
+        previous_authority,
+        new_authority: badge.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct AcceptRewardOwner<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	pub new_authority: Signer<'info>,
+}
+
+/// Accept a pending ownership proposal for a reward
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` reward: [Reward] 
+/// 3. `[signer]` new_authority: [AccountInfo] The proposed owner taking over the reward
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward belongs to
+/// - reward_id: [u64] The reward ID to accept
+pub fn handler(
+	ctx: Context<AcceptRewardOwner>,
+	badge_id: u64,
+	reward_id: u64,
+) -> Result<()> {
+    // Validate that the reward exists and the signer is the proposed owner
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    require!(ctx.accounts.reward.pending_authority == Some(ctx.accounts.new_authority.key()), BadgeRewardsError::NotPendingOwner);
+    
+    // Hand over the reward; the PDA stays keyed by the original owner
+    let reward = &mut ctx.accounts.reward;
+    let previous_authority = reward.authority;
+    reward.authority = ctx.accounts.new_authority.key();
+    reward.pending_authority = None;
+    
+    emit_cpi!(RewardOwnerTransferred {
+        owner: reward.owner,
+        badge_id,
+        reward_id,
+        previous_authority,
+        new_authority: reward.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	user: Pubkey,
+)]
+pub struct AwardPoints<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		init_if_needed,
+		space=8 + UserPoints::INIT_SPACE,
+		payer=fee_payer,
+		seeds = [
+			b"user_points",
+			owner.key().as_ref(),
+			user.as_ref(),
+		],
+		bump,
+	)]
+	pub user_points: Account<'info, UserPoints>,
+
+	pub owner: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+/// Award points to a user
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` user_points: [UserPoints] 
+/// 3. `[signer]` owner: [AccountInfo] The badge owner awarding the points
+/// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 6. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - user: [Pubkey] The user receiving the points
+/// - amount: [u64] Number of points to award
+pub fn handler(
+	ctx: Context<AwardPoints>,
+	user: Pubkey,
+	amount: u64,
+) -> Result<()> {
+    // Validate inputs
+    require!(amount > 0, BadgeRewardsError::InvalidPointsAmount);
+    
+    // Initialize the points account on first award
+    let user_points = &mut ctx.accounts.user_points;
+    if user_points.owner == Pubkey::default() {
+        user_points.version = USER_POINTS_VERSION;
+        user_points.owner = ctx.accounts.owner.key();
+        user_points.user = user;
+        user_points.points = 0;
+        user_points.bump = ctx.bumps.user_points;
+    }
+    require!(user_points.version == USER_POINTS_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+    
+    // Credit the points
+    user_points.points = user_points.points
+        .checked_add(amount)
+        .ok_or(BadgeRewardsError::PointsOverflow)?;
+    
+    emit_cpi!(PointsAwarded {
+        owner: ctx.accounts.owner.key(),
+        user,
+        amount,
+        balance: ctx.accounts.user_points.points,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
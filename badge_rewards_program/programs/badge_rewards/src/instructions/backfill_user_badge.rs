@@ -0,0 +1,111 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	user: Pubkey,
+)]
+pub struct BackfillUserBadge<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_badge",
+			user.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = user_badge.bump,
+		constraint = user_badge.version == USER_BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_badge: Account<'info, UserBadge>,
+
+	pub authority: Signer<'info>,
+
+	pub user_signer: Option<Signer<'info>>,
+}
+
+/// Bind a migrated user badge to the badge it was issued from
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` badge: [Badge] The badge the user badge was issued from
+/// 3. `[writable]` user_badge: [UserBadge] A migrated user badge not yet bound to a badge
+/// 4. `[signer]` authority: [AccountInfo] The badge's authority
+/// 5. `[signer, optional]` user_signer: [AccountInfo] The user, required to co-sign if the user badge has no soulbound NFT
+/// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 7. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID the user badge was issued from
+/// - user: [Pubkey] The user holding the badge
+pub fn handler(
+	ctx: Context<BackfillUserBadge>,
+	badge_id: u64,
+	user: Pubkey,
+) -> Result<()> {
+    // Validate that the signer is the badge's authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.authority.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    
+    // Only user badges migrated from a layout without the badge can be bound, and only once
+    require!(ctx.accounts.user_badge.user == user, BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.badge == Pubkey::default(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Badge ids are only unique per owner, so the badge must be shown to be the one the user
+    // badge was issued from: its soulbound NFT mint is derived from the badge, otherwise the
+    // user co-signs as at issue
+    match ctx.accounts.user_badge.nft_mint {
+        Some(nft_mint) => {
+            let (badge_mint, _) = Pubkey::find_program_address(&[b"badge_mint", ctx.accounts.badge.key().as_ref(), user.as_ref()], &crate::ID);
+            require_keys_eq!(nft_mint, badge_mint, BadgeRewardsError::UserBadgeMismatch);
+        }
+        None => {
+            let user_signer = ctx.accounts.user_signer.as_ref().ok_or(BadgeRewardsError::Unauthorized)?;
+            require_keys_eq!(user_signer.key(), user, BadgeRewardsError::Unauthorized);
+        }
+    }
+    
+    ctx.accounts.user_badge.badge = ctx.accounts.badge.key();
+    
+    // Start the badge's yield from now rather than paying out what accrued before the binding
+    ctx.accounts.user_badge.yield_debt = ctx.accounts.badge.acc_yield_per_badge;
+    
+    emit_cpi!(UserBadgeBackfilled {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user,
+        badge: ctx.accounts.badge.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
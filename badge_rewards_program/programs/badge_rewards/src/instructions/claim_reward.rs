@@ -2,10 +2,15 @@ use crate::*;
 use anchor_lang::prelude::*;
 
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    },
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
     owner: Pubkey,
@@ -20,6 +25,16 @@ pub struct ClaimReward<'info> {
     )]
     pub fee_payer: Signer<'info>,
 
+    #[account(
+        seeds = [
+            b"config",
+        ],
+        bump = config.bump,
+        constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+        constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         seeds = [
             b"badge",
@@ -27,6 +42,7 @@ pub struct ClaimReward<'info> {
             badge_id.to_le_bytes().as_ref(),
         ],
         bump = badge.bump,
+        constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
     )]
     pub badge: Account<'info, Badge>,
 
@@ -39,12 +55,13 @@ pub struct ClaimReward<'info> {
             reward_id.to_le_bytes().as_ref(),
         ],
         bump = reward.bump,
+        constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
     )]
     pub reward: Account<'info, Reward>,
 
     #[account(
         init,
-        space = 40,
+        space = 8 + UserReward::INIT_SPACE,
         payer = fee_payer,
         seeds = [
             b"user_reward",
@@ -55,6 +72,7 @@ pub struct ClaimReward<'info> {
     )]
     pub user_reward: Account<'info, UserReward>,
 
+    /// CHECK: The user's badge; closed once revoked, so it is loaded after the revocation check
     #[account(
         mut,
         seeds = [
@@ -62,44 +80,97 @@ pub struct ClaimReward<'info> {
             user.key().as_ref(),
             badge_id.to_le_bytes().as_ref(),
         ],
-        bump = user_badge.bump,
+        bump,
+    )]
+    pub user_badge: UncheckedAccount<'info>,
+
+    /// CHECK: Revocation record for the badge and user; only read if one has been created
+    #[account(
+        seeds = [
+            b"revoked_badge",
+            badge.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
     )]
-    pub user_badge: Account<'info, UserBadge>,
+    pub revoked_badge: UncheckedAccount<'info>,
 
     #[account(
         mut,
+        token::mint = reward_mint,
+        token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
+        seeds = [
+            b"reward_vault",
+            reward.key().as_ref(),
+        ],
+        bump,
+        token::mint = reward_mint,
     )]
-    pub reward_token_account: Account<'info, TokenAccount>,
+    pub reward_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// The mint for the reward token
-    pub reward_mint: Account<'info, Mint>,
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        payer = fee_payer,
+        seeds = [
+            b"vesting_escrow",
+            reward.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub vesting_escrow: Option<Box<Account<'info, VestingEscrow>>>,
+
+    #[account(
+        init,
+        payer = fee_payer,
+        seeds = [
+            b"vesting_vault",
+            reward.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+        token::mint = reward_mint,
+        token::authority = vesting_escrow,
+        token::token_program = token_program,
+    )]
+    pub vesting_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     pub user: Signer<'info>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 /// Claim a reward for a badge
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[signer]` user: [AccountInfo] The user claiming the reward
-/// 2. `[writable]` user_reward: [UserReward] 
-/// 3. `[writable]` reward: [Reward] 
-/// 4. `[writable]` badge: [Badge] 
-/// 5. `[writable]` user_badge: [UserBadge] 
-/// 6. `[writable]` user_token_account: [AccountInfo] User's token account for reward transfer
-/// 7. `[writable]` reward_token_account: [AccountInfo] Reward token account for transfer
-/// 8. `[]` reward_mint: [Mint] The token mint for the reward
-/// 9. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
-/// 10. `[]` associated_token_program: [AccountInfo] Auto-generated, for associated token program
-/// 11. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[signer]` user: [AccountInfo] The user claiming the reward
+/// 3. `[writable]` user_reward: [UserReward] 
+/// 4. `[writable]` reward: [Reward] 
+/// 5. `[writable]` badge: [Badge] 
+/// 6. `[writable]` user_badge: [UserBadge] The user's badge, closed if it was revoked
+/// 7. `[]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+/// 8. `[writable, optional]` user_token_account: [AccountInfo] User's token account for reward transfer
+/// 9. `[writable, optional]` reward_token_account: [AccountInfo] The reward vault, required for token and NFT rewards
+/// 10. `[optional]` reward_mint: [Mint] The token mint for the reward
+/// 11. `[writable, optional]` vesting_escrow: [VestingEscrow] The user's vesting escrow, required if the reward vests
+/// 12. `[writable, optional]` vesting_vault: [TokenAccount] Token account holding the escrowed tokens, required if the reward vests
+/// 13. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 14. `[]` associated_token_program: [AccountInfo] Auto-generated, for associated token program
+/// 15. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 16. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 17. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - owner: [Pubkey] The owner of the badge
@@ -124,8 +195,27 @@ pub fn handler(
     require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
     require!(ctx.accounts.reward.owner == owner, BadgeRewardsError::Unauthorized);
     
-    // Validate that the reward is active
+    // Validate that the reward is active and not held back by the business
     require!(ctx.accounts.reward.is_active, BadgeRewardsError::RewardNotActive);
+    require!(ctx.accounts.reward.stake_status == StakeStatus::Available, BadgeRewardsError::RewardNotAvailable);
+    
+    // Validate that the reward's claim window is open
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(claim_start) = ctx.accounts.reward.claim_start {
+        require!(now >= claim_start, BadgeRewardsError::ClaimWindowNotOpen);
+    }
+    if let Some(claim_end) = ctx.accounts.reward.claim_end {
+        require!(now < claim_end, BadgeRewardsError::ClaimWindowClosed);
+    }
+    
+    // Validate that a vesting escrow is only created for a reward that vests
+    let vesting = ctx.accounts.reward.vesting;
+    require!(vesting.is_some() || ctx.accounts.vesting_escrow.is_none(), BadgeRewardsError::InvalidVestingEscrow);
+    
+    // Validate that the reward's claim supply has not run out
+    if let Some(max_claims) = ctx.accounts.reward.max_claims {
+        require!(ctx.accounts.reward.total_claimed < max_claims, BadgeRewardsError::RewardClaimLimitReached);
+    }
     
     // Validate that the reward type matches
     require!(ctx.accounts.reward.reward_type == reward_type, BadgeRewardsError::InvalidRewardType);
@@ -133,9 +223,25 @@ pub fn handler(
     // Validate that the reward value matches
     require!(ctx.accounts.reward.reward_value == reward_value, BadgeRewardsError::InvalidRewardValue);
     
+    // Validate that the user's badge has not been revoked since it was issued;
+    // revoking closes the user_badge, so this has to be checked first
+    if let Some(revoked_badge) = RevokedBadge::load(&ctx.accounts.revoked_badge)? {
+        require!(revoked_badge.reissued_at.is_some(), BadgeRewardsError::BadgeRevoked);
+    }
+    
     // Validate that the user has the badge
-    require!(ctx.accounts.user_badge.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
-    require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    let user_badge = UserBadge::load(&ctx.accounts.user_badge)?.ok_or(BadgeRewardsError::BadgeNotFound)?;
+    require!(user_badge.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
+    require!(user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(user_badge.badge == ctx.accounts.badge.key(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Validate that the user's badge is still valid
+    if let Some(expires_at) = user_badge.expires_at {
+        require!(now < expires_at, BadgeRewardsError::BadgeExpired);
+    }
+    
+    // Validate that the user's badge has reached the tier this reward requires
+    require!(user_badge.tier >= ctx.accounts.reward.min_tier, BadgeRewardsError::InsufficientTier);
     
     // Validate that the user hasn't already claimed this reward
     // Check if the user_reward account is already initialized with the same user
@@ -144,30 +250,110 @@ pub fn handler(
         require!(ctx.accounts.user_reward.user != ctx.accounts.user.key(), BadgeRewardsError::RewardAlreadyClaimed);
     }
     
+    // Token and NFT rewards are paid from the reward vault, signed by the reward PDA
+    let badge_id_bytes = badge_id.to_le_bytes();
+    let reward_id_bytes = reward_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"reward",
+        owner.as_ref(),
+        badge_id_bytes.as_ref(),
+        reward_id_bytes.as_ref(),
+        &[ctx.accounts.reward.bump],
+    ]];
+    
     // Transfer reward based on reward type
     match reward_type {
         0 => {
             // Token reward - transfer tokens
             require!(ctx.accounts.reward.token_mint.is_some(), BadgeRewardsError::MissingTokenMint);
             let token_mint = ctx.accounts.reward.token_mint.unwrap();
+            let reward_mint = ctx.accounts.reward_mint.as_ref().ok_or(BadgeRewardsError::MissingTokenMint)?;
+            let reward_token_account = ctx.accounts.reward_token_account.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+            
+            // Vesting rewards are paid into the user's vesting escrow instead of their token account
+            let destination = if vesting.is_some() {
+                ctx.accounts.vesting_vault.as_ref().ok_or(BadgeRewardsError::MissingVestingEscrow)?.to_account_info()
+            } else {
+                ctx.accounts.user_token_account.as_ref().ok_or(BadgeRewardsError::InvalidTokenAccount)?.to_account_info()
+            };
             
             // Validate that the reward mint matches
-            require!(ctx.accounts.reward_mint.key() == token_mint, BadgeRewardsError::InvalidTokenMint);
+            require!(reward_mint.key() == token_mint, BadgeRewardsError::InvalidTokenMint);
+            
+            // Gross up for any Token-2022 transfer fee so the user receives reward_value; a vesting
+            // reward is grossed up twice, covering the fee on the later withdrawals from the escrow
+            let mut transfer_amount = pre_fee_amount(&reward_mint.to_account_info(), reward_value)?;
+            if vesting.is_some() {
+                transfer_amount = pre_fee_amount(&reward_mint.to_account_info(), transfer_amount)?;
+            }
+            require!(reward_token_account.amount >= transfer_amount, BadgeRewardsError::InsufficientVaultBalance);
             
-            // Transfer tokens from reward token account to user token account
-            let cpi_accounts = anchor_spl::token::Transfer {
-                from: ctx.accounts.reward_token_account.to_account_info(),
-                to: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.badge.to_account_info(),
+            // Transfer tokens from the reward vault to the user token account or vesting escrow
+            let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+                from: reward_token_account.to_account_info(),
+                mint: reward_mint.to_account_info(),
+                to: destination,
+                authority: ctx.accounts.reward.to_account_info(),
             };
             let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            anchor_spl::token::transfer(cpi_ctx, reward_value)?;
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            anchor_spl::token_interface::transfer_checked(cpi_ctx, transfer_amount, reward_mint.decimals)?;
+            
+            // Start the user's vesting schedule from now
+            if let Some(vesting) = vesting {
+                let badge_key = ctx.accounts.badge.key();
+                let reward_key = ctx.accounts.reward.key();
+                let user_key = ctx.accounts.user.key();
+                let (cliff_at, end_at) = vesting.times_from(now);
+                ctx.accounts.reward.vesting_ends_at = ctx.accounts.reward.vesting_ends_at.max(end_at);
+                let vesting_escrow = ctx.accounts.vesting_escrow.as_mut().ok_or(BadgeRewardsError::MissingVestingEscrow)?;
+                vesting_escrow.version = VESTING_ESCROW_VERSION;
+                vesting_escrow.user = user_key;
+                vesting_escrow.reward = reward_key;
+                vesting_escrow.badge = badge_key;
+                vesting_escrow.mint = token_mint;
+                vesting_escrow.total_amount = reward_value;
+                vesting_escrow.withdrawn_amount = 0;
+                vesting_escrow.start_at = now;
+                vesting_escrow.cliff_at = cliff_at;
+                vesting_escrow.end_at = end_at;
+                vesting_escrow.bump = ctx.bumps.vesting_escrow.ok_or(BadgeRewardsError::MissingVestingEscrow)?;
+            }
         },
         1 => {
-            // NFT reward - transfer NFT (this would require more complex logic)
-            // For now, we'll just validate that the NFT mint is provided
-            require!(ctx.accounts.reward.nft_mint.is_some(), BadgeRewardsError::MissingNftMint);
+            // NFT reward - transfer the escrowed NFT
+            let nft_mint = ctx.accounts.reward.nft_mint.ok_or(BadgeRewardsError::MissingNftMint)?;
+            let reward_mint = ctx.accounts.reward_mint.as_ref().ok_or(BadgeRewardsError::MissingNftMint)?;
+            let reward_token_account = ctx.accounts.reward_token_account.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+            let user_token_account = ctx.accounts.user_token_account.as_ref().ok_or(BadgeRewardsError::InvalidTokenAccount)?;
+            
+            // Validate that the reward mint matches and is a 0-decimal, supply-1 mint
+            require!(reward_mint.key() == nft_mint, BadgeRewardsError::InvalidNftMint);
+            require!(reward_mint.decimals == 0 && reward_mint.supply == 1, BadgeRewardsError::InvalidNftMint);
+            
+            // Validate that the NFT goes to the user's associated token account
+            require!(
+                user_token_account.key() == get_associated_token_address_with_program_id(
+                    &ctx.accounts.user.key(),
+                    &nft_mint,
+                    &ctx.accounts.token_program.key(),
+                ),
+                BadgeRewardsError::InvalidTokenAccount
+            );
+            
+            // Transfer the NFT from the reward vault
+            let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+                from: reward_token_account.to_account_info(),
+                mint: reward_mint.to_account_info(),
+                to: user_token_account.to_account_info(),
+                authority: ctx.accounts.reward.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+            anchor_spl::token_interface::transfer_checked(cpi_ctx, 1, reward_mint.decimals)?;
+            
+            // A single NFT can only be claimed once
+            ctx.accounts.reward.is_active = false;
         },
         2 => {
             // Other reward - no transfer needed
@@ -178,10 +364,54 @@ pub fn handler(
     }
     
     // Record that the user has claimed this reward
+    ctx.accounts.user_reward.version = USER_REWARD_VERSION;
     ctx.accounts.user_reward.user = ctx.accounts.user.key();
     ctx.accounts.user_reward.reward_id = reward_id;
-    ctx.accounts.user_reward.claimed_at = Clock::get()?.unix_timestamp;
+    ctx.accounts.user_reward.claimed_at = now;
+    ctx.accounts.user_reward.claimed_amount = match reward_type {
+        0 => reward_value,
+        1 => 1,
+        _ => 0,
+    };
+    ctx.accounts.user_reward.stake_status = StakeStatus::Available;
+    ctx.accounts.user_reward.staked_at = 0;
+    ctx.accounts.user_reward.min_stake_period = 0;
+    ctx.accounts.user_reward.can_unstake_at = 0;
+    ctx.accounts.user_reward.locked_amount = 0;
     ctx.accounts.user_reward.bump = ctx.bumps.user_reward;
+    ctx.accounts.user_reward.reward = ctx.accounts.reward.key();
+    ctx.accounts.user_reward.owner = owner;
+    
+    // Count the claim and deactivate the reward once its supply is used up
+    ctx.accounts.reward.total_claimed = ctx.accounts.reward.total_claimed.saturating_add(1);
+    if ctx.accounts.reward.max_claims == Some(ctx.accounts.reward.total_claimed) {
+        ctx.accounts.reward.is_active = false;
+    }
+    
+    emit_cpi!(RewardClaimed {
+        owner,
+        badge_id,
+        reward_id,
+        user: ctx.accounts.user.key(),
+        reward_type,
+        amount: ctx.accounts.user_reward.claimed_amount,
+        total_claimed: ctx.accounts.reward.total_claimed,
+        timestamp: ctx.accounts.user_reward.claimed_at,
+    });
     
     Ok(())
+}
+
+/// Amount to send so that `amount` arrives after any Token-2022 transfer fee
+/// configured on the mint
+pub(crate) fn pre_fee_amount(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .get_epoch_fee(Clock::get()?.epoch)
+            .calculate_pre_fee_amount(amount)
+            .ok_or_else(|| BadgeRewardsError::TransferFeeOverflow.into()),
+        Err(_) => Ok(amount),
+    }
 }
\ No newline at end of file
@@ -0,0 +1,162 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct CloseBadge<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		close = badge_owner,
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	#[account(
+		mut,
+	)]
+	pub badge_owner: Signer<'info>,
+
+	#[account(
+		init_if_needed,
+		space = 8 + ClosedBadge::INIT_SPACE,
+		payer = fee_payer,
+		seeds = [
+			b"closed_badge",
+			badge.key().as_ref(),
+		],
+		bump,
+	)]
+	pub closed_badge: Account<'info, ClosedBadge>,
+
+	/// The token mint yield is paid in
+	#[account(
+		mut,
+		constraint = badge.yield_mint == Some(yield_mint.key()) @ BadgeRewardsError::YieldMintMismatch,
+	)]
+	pub yield_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"yield_vault",
+			badge.key().as_ref(),
+		],
+		bump,
+		token::mint = yield_mint,
+	)]
+	pub yield_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	pub token_program: Option<Interface<'info, TokenInterface>>,
+
+	pub system_program: Program<'info, System>,
+}
+
+/// Close a retired badge and reclaim its rent
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[writable, signer]` badge_owner: [AccountInfo] The authority of the badge, receives its rent
+/// 4. `[writable]` closed_badge: [ClosedBadge] Record carrying the badge's issued count over to a re-created badge
+/// 5. `[writable, optional]` yield_mint: [Mint] The token mint yield is paid in, receives the yield vault's withheld transfer fees
+/// 6. `[writable, optional]` yield_vault: [TokenAccount] The badge's yield vault, required if a yield mint was set
+/// 7. `[optional]` token_program: [AccountInfo] Token program of the yield vault, required if a yield mint was set
+/// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 10. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to close
+pub fn handler(
+	ctx: Context<CloseBadge>,
+	badge_id: u64,
+) -> Result<()> {
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    
+    // Closing a badge with holders would strand their user_badges, and one that has been issued
+    // must first stay deactivated for the grace period
+    let now = Clock::get()?.unix_timestamp;
+    require!(ctx.accounts.badge.total_earned == 0, BadgeRewardsError::BadgeHasHolders);
+    if ctx.accounts.badge.total_issued > 0 {
+        require!(!ctx.accounts.badge.is_active, BadgeRewardsError::BadgeCloseGracePeriod);
+        require!(
+            now >= ctx.accounts.badge.deactivated_at.saturating_add(BADGE_CLOSE_GRACE_PERIOD),
+            BadgeRewardsError::BadgeCloseGracePeriod
+        );
+    }
+    
+    // Record the issued count so re-creating the badge at the same address keeps its supply cap
+    let closed_badge = &mut ctx.accounts.closed_badge;
+    closed_badge.version = CLOSED_BADGE_VERSION;
+    closed_badge.owner = ctx.accounts.badge.owner;
+    closed_badge.badge_id = badge_id;
+    closed_badge.total_issued = ctx.accounts.badge.total_issued;
+    closed_badge.closed_at = now;
+    closed_badge.bump = ctx.bumps.closed_badge;
+    
+    // Setting a yield mint created the badge's yield vault, which must be emptied before it is closed
+    if ctx.accounts.badge.yield_mint.is_some() {
+        let yield_mint = ctx.accounts.yield_mint.as_ref().ok_or(BadgeRewardsError::MissingYieldAccounts)?;
+        let yield_vault = ctx.accounts.yield_vault.as_ref().ok_or(BadgeRewardsError::MissingYieldAccounts)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingYieldAccounts)?;
+        require!(yield_vault.amount == 0, BadgeRewardsError::YieldVaultNotEmpty);
+        
+        // Close the empty vault, returning its rent to the badge_owner, signed by the badge PDA
+        let badge_owner = ctx.accounts.badge.owner;
+        let badge_id_bytes = badge_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"badge",
+            badge_owner.as_ref(),
+            badge_id_bytes.as_ref(),
+            &[ctx.accounts.badge.bump],
+        ]];
+        close_token_account(
+            yield_vault.to_account_info(),
+            Some(yield_mint.to_account_info()),
+            ctx.accounts.badge_owner.to_account_info(),
+            ctx.accounts.badge.to_account_info(),
+            token_program.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+    
+    // The badge account is closed on exit and its rent returned to the badge_owner
+    
+    emit_cpi!(BadgeClosed {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        holders: ctx.accounts.badge.total_earned,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
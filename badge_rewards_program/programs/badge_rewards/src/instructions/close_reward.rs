@@ -0,0 +1,211 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+    },
+    token_2022_extensions::transfer_fee::{harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct CloseReward<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		close = owner,
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	#[account(
+		mut,
+	)]
+	pub owner: Signer<'info>,
+
+	/// CHECK: The reward vault, may be uninitialized if the reward was never funded
+	#[account(
+		mut,
+		seeds = [
+			b"reward_vault",
+			reward.key().as_ref(),
+		],
+		bump,
+	)]
+	pub reward_vault: Option<UncheckedAccount<'info>>,
+
+	/// The token mint held by the reward vault
+	#[account(
+		mut,
+	)]
+	pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = owner,
+	)]
+	pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Close a retired reward, optionally sweeping its vault back to the owner
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` reward: [Reward] 
+/// 3. `[writable, signer]` owner: [AccountInfo] The authority of the reward, receives the rent
+/// 4. `[writable, optional]` reward_vault: [TokenAccount] The reward vault, required for token and NFT rewards
+/// 5. `[writable, optional]` reward_mint: [Mint] The token mint held by the reward vault, required to sweep it or harvest its withheld transfer fees
+/// 6. `[writable, optional]` owner_token_account: [TokenAccount] The owner's token account to sweep the vault to
+/// 7. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 9. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward is associated with
+/// - reward_id: [u64] The reward ID to close
+/// - sweep: [bool] Whether to return any tokens left in the vault to the owner
+pub fn handler(
+	ctx: Context<CloseReward>,
+	badge_id: u64,
+	reward_id: u64,
+	sweep: bool,
+) -> Result<()> {
+    // Validate that the reward exists and the signer is its authority
+    require!(ctx.accounts.reward.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    
+    // A reward locked by a business stake cannot be closed
+    require!(ctx.accounts.reward.stake_status != StakeStatus::StakedByBusiness, BadgeRewardsError::RewardNotAvailable);
+    
+    // Unvested tokens are reclaimed into the vault, so it must stay open until every escrow has fully vested
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.reward.vesting_ends_at, BadgeRewardsError::VestingInProgress);
+    
+    // Token and NFT rewards must present their vault so it can be checked and closed
+    let has_vault = ctx.accounts.reward.token_mint.is_some() || ctx.accounts.reward.nft_mint.is_some();
+    let mut swept_amount = 0;
+    if has_vault {
+        let reward_vault = ctx.accounts.reward_vault.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+        if !reward_vault.data_is_empty() {
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+            let vault_amount = TokenAccount::try_deserialize(&mut &reward_vault.try_borrow_data()?[..])?.amount;
+            
+            let owner_key = ctx.accounts.reward.owner;
+            let badge_id_bytes = badge_id.to_le_bytes();
+            let reward_id_bytes = reward_id.to_le_bytes();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"reward",
+                owner_key.as_ref(),
+                badge_id_bytes.as_ref(),
+                reward_id_bytes.as_ref(),
+                &[ctx.accounts.reward.bump],
+            ]];
+            
+            // Sweep any remaining tokens back to the owner, signed by the reward PDA
+            if vault_amount > 0 {
+                require!(sweep, BadgeRewardsError::VaultNotEmpty);
+                let reward_mint = ctx.accounts.reward_mint.as_ref().ok_or(BadgeRewardsError::MissingTokenMint)?;
+                let owner_token_account = ctx.accounts.owner_token_account.as_ref().ok_or(BadgeRewardsError::InvalidTokenAccount)?;
+                
+                let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+                    from: reward_vault.to_account_info(),
+                    mint: reward_mint.to_account_info(),
+                    to: owner_token_account.to_account_info(),
+                    authority: ctx.accounts.reward.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+                anchor_spl::token_interface::transfer_checked(cpi_ctx, vault_amount, reward_mint.decimals)?;
+                swept_amount = vault_amount;
+            }
+            
+            // Close the empty vault, returning its rent to the owner
+            close_token_account(
+                reward_vault.to_account_info(),
+                ctx.accounts.reward_mint.as_ref().map(|mint| mint.to_account_info()),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.reward.to_account_info(),
+                token_program.to_account_info(),
+                signer_seeds,
+            )?;
+        }
+    }
+    
+    // The reward account is closed on exit and its rent returned to the owner
+    
+    emit_cpi!(RewardClosed {
+        owner: ctx.accounts.reward.owner,
+        badge_id,
+        reward_id,
+        swept_amount,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+/// Close an empty program-owned token account, first harvesting any Token-2022 transfer fees
+/// withheld in it to the mint, since an account still holding withheld fees cannot be closed
+pub(crate) fn close_token_account<'info>(
+    account: AccountInfo<'info>,
+    mint: Option<AccountInfo<'info>>,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let withheld_amount = {
+        let account_data = account.try_borrow_data()?;
+        let account_state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)?;
+        account_state.get_extension::<TransferFeeAmount>().map_or(0, |fee_amount| u64::from(fee_amount.withheld_amount))
+    };
+    if withheld_amount > 0 {
+        let mint = mint.ok_or(BadgeRewardsError::MissingTokenMint)?;
+        let cpi_accounts = HarvestWithheldTokensToMint {
+            token_program_id: token_program.clone(),
+            mint,
+        };
+        let cpi_ctx = CpiContext::new(token_program.clone(), cpi_accounts);
+        harvest_withheld_tokens_to_mint(cpi_ctx, vec![account.clone()])?;
+    }
+    
+    let cpi_accounts = anchor_spl::token_interface::CloseAccount {
+        account,
+        destination,
+        authority,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+    anchor_spl::token_interface::close_account(cpi_ctx)
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	collection_id: u64,
+)]
+pub struct CompleteCollection<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"collection",
+			collection.owner.as_ref(),
+			collection_id.to_le_bytes().as_ref(),
+		],
+		bump = collection.bump,
+		constraint = collection.version == COLLECTION_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub collection: Account<'info, Collection>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"badge",
+			collection.owner.as_ref(),
+			collection.meta_badge_id.to_le_bytes().as_ref(),
+		],
+		bump = meta_badge.bump,
+		constraint = meta_badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub meta_badge: Account<'info, Badge>,
+
+	#[account(
+		init,
+		space = 8 + UserBadge::INIT_SPACE,
+		payer = fee_payer,
+		seeds = [
+			b"user_badge",
+			user.key().as_ref(),
+			collection.meta_badge_id.to_le_bytes().as_ref(),
+		],
+		bump,
+	)]
+	pub user_badge: Account<'info, UserBadge>,
+
+	/// CHECK: Revocation record for the badge and user; only read if one has been created
+	#[account(
+		mut,
+		seeds = [
+			b"revoked_badge",
+			meta_badge.key().as_ref(),
+			user.key().as_ref(),
+		],
+		bump,
+	)]
+	pub revoked_badge: UncheckedAccount<'info>,
+
+	#[account(
+		seeds = [
+			b"user_points",
+			collection.owner.as_ref(),
+			user.key().as_ref(),
+		],
+		bump = user_points.bump,
+		constraint = user_points.version == USER_POINTS_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_points: Account<'info, UserPoints>,
+
+	pub user: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+/// Award a collection's meta-badge to a user holding every member badge, without the owner co-signing
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` collection: [Collection] 
+/// 3. `[writable]` meta_badge: [Badge] The badge awarded on completion
+/// 4. `[writable]` user_badge: [UserBadge] The user's meta-badge
+/// 5. `[writable]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+/// 6. `[]` user_points: [UserPoints] The user's points balance with the collection owner
+/// 7. `[signer]` user: [AccountInfo] The user completing the collection
+/// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 10. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Remaining accounts:
+/// 0..n. `[]` user_badges: [UserBadge] The user's user_badge for each of the collection's badges, in order
+/// n..m. `[]` prerequisite_user_badges: [UserBadge] The user's user_badge for each of the meta-badge's prerequisites, in order
+///
+/// Data:
+/// - collection_id: [u64] The collection to complete
+pub fn handler(
+	ctx: Context<CompleteCollection>,
+	collection_id: u64,
+) -> Result<()> {
+    // Validate that the meta-badge can still be issued; the owner approved it when creating the collection
+    require!(ctx.accounts.meta_badge.is_active, BadgeRewardsError::BadgeNotActive);
+    require!(!ctx.accounts.meta_badge.mint_nft, BadgeRewardsError::MetaBadgeMintsNft);
+    require!(ctx.accounts.meta_badge.total_issued < ctx.accounts.meta_badge.max_earnings, BadgeRewardsError::MaxEarningsReached);
+    
+    // Validate that the user has earned enough points with the owner, as stake_badge requires
+    require!(ctx.accounts.user_points.points >= ctx.accounts.meta_badge.required_points, BadgeRewardsError::InsufficientPoints);
+    
+    // Validate that the user holds every badge in the collection, passed in order as remaining accounts
+    let now = Clock::get()?.unix_timestamp;
+    let user = ctx.accounts.user.key();
+    let owner = ctx.accounts.collection.owner;
+    for (index, badge_id) in ctx.accounts.collection.badge_ids.iter().enumerate() {
+        let held = match ctx.remaining_accounts.get(index) {
+            Some(info) => UserBadge::load_held(info, &user, &owner, *badge_id, now)?.is_some(),
+            None => false,
+        };
+        if !held {
+            msg!("Missing collection badge {} from owner {}", badge_id, owner);
+            return err!(BadgeRewardsError::CollectionIncomplete);
+        }
+    }
+    
+    // Validate that the user holds every prerequisite of the meta-badge, passed in order after the collection's badges
+    let prerequisite_user_badges = ctx.remaining_accounts.get(ctx.accounts.collection.badge_ids.len()..).unwrap_or(&[]);
+    ctx.accounts.meta_badge.require_prerequisites(&user, prerequisite_user_badges, now)?;
+    
+    // Re-issuing a revoked badge is only allowed if the revocation permits it
+    let revoked_badge_info = ctx.accounts.revoked_badge.to_account_info();
+    if let Some(mut revoked_badge) = RevokedBadge::load(&revoked_badge_info)? {
+        require!(revoked_badge.allow_reissue, BadgeRewardsError::BadgeRevoked);
+        revoked_badge.reissued_at = Some(now);
+        revoked_badge.try_serialize(&mut &mut revoked_badge_info.try_borrow_mut_data()?[..])?;
+    }
+    
+    // Initialize the user's meta-badge
+    ctx.accounts.user_badge.version = USER_BADGE_VERSION;
+    ctx.accounts.user_badge.user = user;
+    ctx.accounts.user_badge.badge_id = ctx.accounts.collection.meta_badge_id;
+    ctx.accounts.user_badge.earned_at = now;
+    ctx.accounts.user_badge.rent_payer = ctx.accounts.fee_payer.key();
+    ctx.accounts.user_badge.nft_mint = None;
+    ctx.accounts.user_badge.bump = ctx.bumps.user_badge;
+    ctx.accounts.user_badge.expires_at = ctx.accounts.meta_badge.expires_at(now);
+    ctx.accounts.user_badge.tier = 0;
+    ctx.accounts.user_badge.badge = ctx.accounts.meta_badge.key();
+    
+    // Accrue yield to the current holders, then start this meta-badge's yield from now
+    ctx.accounts.meta_badge.accrue_yield(now)?;
+    ctx.accounts.user_badge.yield_debt = ctx.accounts.meta_badge.acc_yield_per_badge;
+    
+    // Update meta-badge issued and held counts, and the collection's completions
+    ctx.accounts.meta_badge.total_issued = ctx.accounts.meta_badge.total_issued.saturating_add(1);
+    ctx.accounts.meta_badge.total_earned = ctx.accounts.meta_badge.total_earned.saturating_add(1);
+    ctx.accounts.collection.total_completed = ctx.accounts.collection.total_completed.saturating_add(1);
+    
+    emit_cpi!(CollectionCompleted {
+        owner,
+        collection_id,
+        meta_badge_id: ctx.accounts.collection.meta_badge_id,
+        user,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
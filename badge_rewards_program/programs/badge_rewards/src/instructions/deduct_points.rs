@@ -0,0 +1,78 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	user: Pubkey,
+)]
+pub struct DeductPoints<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_points",
+			owner.key().as_ref(),
+			user.as_ref(),
+		],
+		bump = user_points.bump,
+		constraint = user_points.version == USER_POINTS_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_points: Account<'info, UserPoints>,
+
+	pub owner: Signer<'info>,
+}
+
+/// Deduct points from a user
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` user_points: [UserPoints] 
+/// 3. `[signer]` owner: [AccountInfo] The badge owner deducting the points
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - user: [Pubkey] The user losing the points
+/// - amount: [u64] Number of points to deduct
+pub fn handler(
+	ctx: Context<DeductPoints>,
+	user: Pubkey,
+	amount: u64,
+) -> Result<()> {
+    // Validate that the points account belongs to the owner and user
+    require!(ctx.accounts.user_points.owner == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_points.user == user, BadgeRewardsError::Unauthorized);
+    
+    // Validate inputs
+    require!(amount > 0, BadgeRewardsError::InvalidPointsAmount);
+    require!(ctx.accounts.user_points.points >= amount, BadgeRewardsError::InsufficientPoints);
+    
+    // Debit the points
+    ctx.accounts.user_points.points -= amount;
+    
+    emit_cpi!(PointsDeducted {
+        owner: ctx.accounts.owner.key(),
+        user,
+        amount,
+        balance: ctx.accounts.user_points.points,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
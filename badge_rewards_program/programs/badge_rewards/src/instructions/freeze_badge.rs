@@ -1,6 +1,7 @@
 use crate::*;
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
@@ -11,14 +12,25 @@ pub struct FreezeBadge<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
 	#[account(
 		mut,
 		seeds = [
 			b"badge",
-			badge_owner.key().as_ref(),
+			badge.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub badge: Account<'info, Badge>,
 
@@ -29,9 +41,12 @@ pub struct FreezeBadge<'info> {
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` badge: [Badge] 
-/// 2. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-/// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+/// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 6. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] The badge ID to freeze
@@ -39,12 +54,21 @@ pub fn handler(
 	ctx: Context<FreezeBadge>,
 	badge_id: u64,
 ) -> Result<()> {
-    // Validate that the badge exists and is owned by the owner
-    require!(ctx.accounts.badge.owner == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
     
     // Freeze the badge
+    if ctx.accounts.badge.is_active {
+        ctx.accounts.badge.deactivated_at = Clock::get()?.unix_timestamp;
+    }
     ctx.accounts.badge.is_active = false;
     
+    emit_cpi!(BadgeFrozen {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct FundBadgeYield<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	pub funder: Signer<'info>,
+
+	/// The token mint yield is paid in
+	#[account(
+		constraint = badge.yield_mint == Some(yield_mint.key()) @ BadgeRewardsError::YieldMintMismatch,
+	)]
+	pub yield_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		token::mint = yield_mint,
+		token::authority = funder,
+	)]
+	pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"yield_vault",
+			badge.key().as_ref(),
+		],
+		bump,
+		token::mint = yield_mint,
+	)]
+	pub yield_vault: InterfaceAccount<'info, TokenAccount>,
+
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Deposit tokens into a badge's yield vault (anyone may fund)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` badge: [Badge] 
+/// 3. `[signer]` funder: [AccountInfo] The wallet depositing the tokens
+/// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+/// 5. `[writable]` funder_token_account: [TokenAccount] The funder's token account to fund from
+/// 6. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+/// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 9. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to fund yield for
+/// - amount: [u64] Number of tokens to deposit
+pub fn handler(
+	ctx: Context<FundBadgeYield>,
+	badge_id: u64,
+	amount: u64,
+) -> Result<()> {
+    require!(amount > 0, BadgeRewardsError::InvalidAmount);
+    
+    // Transfer tokens from the funder to the yield vault
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        mint: ctx.accounts.yield_mint.to_account_info(),
+        to: ctx.accounts.yield_vault.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.yield_mint.decimals)?;
+    
+    ctx.accounts.yield_vault.reload()?;
+    emit_cpi!(BadgeYieldFunded {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        amount,
+        vault_balance: ctx.accounts.yield_vault.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct FundReward<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	pub owner: Signer<'info>,
+
+	/// The token mint for the reward
+	pub reward_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = owner,
+	)]
+	pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+	#[account(
+		init_if_needed,
+		payer = fee_payer,
+		seeds = [
+			b"reward_vault",
+			reward.key().as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+		token::authority = reward,
+	)]
+	pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+	pub system_program: Program<'info, System>,
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Deposit tokens into a reward's vault
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` reward: [Reward] 
+/// 3. `[signer]` owner: [AccountInfo] The owner of the reward
+/// 4. `[]` reward_mint: [Mint] The token mint for the reward
+/// 5. `[writable]` owner_token_account: [TokenAccount] The owner's token account to fund from
+/// 6. `[writable]` reward_vault: [TokenAccount] Program-owned vault claims are paid from
+/// 7. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 8. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 10. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward is associated with
+/// - reward_id: [u64] The reward ID
+/// - amount: [u64] Number of tokens to deposit
+pub fn handler(
+	ctx: Context<FundReward>,
+	badge_id: u64,
+	reward_id: u64,
+	amount: u64,
+) -> Result<()> {
+    // Validate that the reward exists and the signer is its authority
+    require!(ctx.accounts.reward.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    
+    // Validate that this is a token reward for the given mint
+    require!(ctx.accounts.reward.reward_type == 0, BadgeRewardsError::InvalidRewardType);
+    require!(ctx.accounts.reward.token_mint == Some(ctx.accounts.reward_mint.key()), BadgeRewardsError::InvalidTokenMint);
+    require!(amount > 0, BadgeRewardsError::InvalidAmount);
+    
+    // Transfer tokens from the owner to the reward vault
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: ctx.accounts.owner_token_account.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+    
+    ctx.accounts.reward_vault.reload()?;
+    emit_cpi!(RewardFunded {
+        owner: ctx.accounts.owner.key(),
+        badge_id,
+        reward_id,
+        amount,
+        vault_balance: ctx.accounts.reward_vault.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -2,22 +2,61 @@ use crate::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
-pub struct GetUserBadges<'info> {
-	#[account(
-		mut,
-	)]
-	pub fee_payer: Signer<'info>,
+pub struct GetUserBadges {}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UserBadgeSummary {
+	pub user_badge: Pubkey,
+	pub badge_id: u64,
+	pub earned_at: i64,
+	pub tier: u8,
 }
 
 /// Get all badges earned by a user
 ///
 /// Accounts:
-/// 0. `[writable, signer]` fee_payer: [AccountInfo] Auto-generated, default fee payer
+/// 0..n. `[]` remaining_accounts: [UserBadge] Candidate user_badge accounts for the user
 ///
 /// Data:
 /// - user: [Pubkey] The user to get badges for
-pub fn handler(ctx: Context<GetUserBadges>, user: Pubkey) -> Result<()> {
-    // This is a read-only function that would typically return a list of badges
-    // For now, we'll just return an empty result
-    Ok(())
+///
+/// Returns the held badges as a Borsh-encoded [Vec<UserBadgeSummary>] in the
+/// transaction return data. Candidates that have been closed are skipped.
+pub fn handler(ctx: Context<GetUserBadges>, user: Pubkey) -> Result<Vec<UserBadgeSummary>> {
+    require!(ctx.remaining_accounts.len() <= MAX_QUERY_ACCOUNTS, BadgeRewardsError::TooManyAccounts);
+    
+    let mut badges = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        // Skip candidates that were never created or have been closed
+        if info.data_is_empty() {
+            continue;
+        }
+        
+        // Validate that the account is a UserBadge owned by this program
+        require_keys_eq!(*info.owner, crate::ID, BadgeRewardsError::InvalidUserBadge);
+        let user_badge = UserBadge::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(user_badge.version == USER_BADGE_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+        
+        // Validate that the account is the user's PDA for this badge
+        require_keys_eq!(user_badge.user, user, BadgeRewardsError::InvalidUserBadge);
+        let expected = Pubkey::create_program_address(
+            &[
+                b"user_badge",
+                user.as_ref(),
+                user_badge.badge_id.to_le_bytes().as_ref(),
+                &[user_badge.bump],
+            ],
+            &crate::ID,
+        ).map_err(|_| BadgeRewardsError::InvalidUserBadge)?;
+        require_keys_eq!(expected, info.key(), BadgeRewardsError::InvalidUserBadge);
+        
+        badges.push(UserBadgeSummary {
+            user_badge: info.key(),
+            badge_id: user_badge.badge_id,
+            earned_at: user_badge.earned_at,
+            tier: user_badge.tier,
+        });
+    }
+    
+    Ok(badges)
 }
\ No newline at end of file
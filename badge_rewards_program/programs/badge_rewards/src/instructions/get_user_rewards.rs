@@ -2,22 +2,59 @@ use crate::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
-pub struct GetUserRewards<'info> {
-	#[account(
-		mut,
-	)]
-	pub fee_payer: Signer<'info>,
+pub struct GetUserRewards {}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UserRewardSummary {
+	pub user_reward: Pubkey,
+	pub reward_id: u64,
+	pub claimed_at: i64,
 }
 
 /// Get all rewards claimed by a user
 ///
 /// Accounts:
-/// 0. `[writable, signer]` fee_payer: [AccountInfo] Auto-generated, default fee payer
+/// 0..n. `[]` remaining_accounts: [UserReward] Candidate user_reward accounts for the user
 ///
 /// Data:
 /// - user: [Pubkey] The user to get rewards for
-pub fn handler(ctx: Context<GetUserRewards>, user: Pubkey) -> Result<()> {
-    // This is a read-only function that would typically return a list of rewards
-    // For now, we'll just return an empty result
-    Ok(())
+///
+/// Returns the claimed rewards as a Borsh-encoded [Vec<UserRewardSummary>] in the
+/// transaction return data. Candidates that have been closed are skipped.
+pub fn handler(ctx: Context<GetUserRewards>, user: Pubkey) -> Result<Vec<UserRewardSummary>> {
+    require!(ctx.remaining_accounts.len() <= MAX_QUERY_ACCOUNTS, BadgeRewardsError::TooManyAccounts);
+    
+    let mut rewards = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        // Skip candidates that were never created or have been closed
+        if info.data_is_empty() {
+            continue;
+        }
+        
+        // Validate that the account is a UserReward owned by this program
+        require_keys_eq!(*info.owner, crate::ID, BadgeRewardsError::InvalidUserReward);
+        let user_reward = UserReward::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(user_reward.version == USER_REWARD_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+        
+        // Validate that the account is the user's PDA for this reward
+        require_keys_eq!(user_reward.user, user, BadgeRewardsError::InvalidUserReward);
+        let expected = Pubkey::create_program_address(
+            &[
+                b"user_reward",
+                user.as_ref(),
+                user_reward.reward_id.to_le_bytes().as_ref(),
+                &[user_reward.bump],
+            ],
+            &crate::ID,
+        ).map_err(|_| BadgeRewardsError::InvalidUserReward)?;
+        require_keys_eq!(expected, info.key(), BadgeRewardsError::InvalidUserReward);
+        
+        rewards.push(UserRewardSummary {
+            user_reward: info.key(),
+            reward_id: user_reward.reward_id,
+            claimed_at: user_reward.claimed_at,
+        });
+    }
+    
+    Ok(rewards)
 }
\ No newline at end of file
@@ -0,0 +1,188 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct Harvest<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_badge",
+			user.key().as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = user_badge.bump,
+		constraint = user_badge.version == USER_BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_badge: Account<'info, UserBadge>,
+
+	/// The token mint yield is paid in
+	#[account(
+		constraint = badge.yield_mint == Some(yield_mint.key()) @ BadgeRewardsError::YieldMintMismatch,
+	)]
+	pub yield_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"yield_vault",
+			badge.key().as_ref(),
+		],
+		bump,
+		token::mint = yield_mint,
+	)]
+	pub yield_vault: InterfaceAccount<'info, TokenAccount>,
+
+	#[account(
+		mut,
+		token::mint = yield_mint,
+		token::authority = user,
+	)]
+	pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+	pub user: Signer<'info>,
+
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pay out the yield a held badge has accrued since it was earned or last harvested
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[writable]` user_badge: [UserBadge] 
+/// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+/// 5. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+/// 6. `[writable]` user_token_account: [TokenAccount] The user's token account to pay the yield to
+/// 7. `[signer]` user: [AccountInfo] The user holding the badge
+/// 8. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 10. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to harvest yield from
+pub fn handler(
+	ctx: Context<Harvest>,
+	badge_id: u64,
+) -> Result<()> {
+    // Validate that the user has the badge
+    require!(ctx.accounts.user_badge.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.badge == ctx.accounts.badge.key(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Bring the accumulator up to date and work out this badge's share since it was last settled
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.badge.accrue_yield(now)?;
+    let acc_yield_per_badge = ctx.accounts.badge.acc_yield_per_badge;
+    let amount = ctx.accounts.user_badge.pending_yield(acc_yield_per_badge);
+    require!(amount > 0, BadgeRewardsError::NothingToHarvest);
+    ctx.accounts.user_badge.yield_debt = acc_yield_per_badge;
+    ctx.accounts.badge.reserved_yield = ctx.accounts.badge.reserved_yield.saturating_sub(amount);
+    
+    pay_yield(
+        &ctx.accounts.badge,
+        &ctx.accounts.yield_mint,
+        &ctx.accounts.yield_vault,
+        ctx.accounts.user_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    
+    emit_cpi!(YieldHarvested {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user: ctx.accounts.user.key(),
+        amount,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
+
+/// Transfer `amount` from a badge's yield vault to `to`, signed by the badge PDA
+pub(crate) fn pay_yield<'info>(
+    badge: &Account<'info, Badge>,
+    yield_mint: &InterfaceAccount<'info, Mint>,
+    yield_vault: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    require!(yield_vault.amount >= amount, BadgeRewardsError::InsufficientVaultBalance);
+    
+    let badge_id_bytes = badge.badge_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"badge",
+        badge.owner.as_ref(),
+        badge_id_bytes.as_ref(),
+        &[badge.bump],
+    ]];
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: yield_vault.to_account_info(),
+        mint: yield_mint.to_account_info(),
+        to,
+        authority: badge.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, yield_mint.decimals)
+}
+
+/// Pay out a departing user badge's unharvested yield, returning the amount paid
+///
+/// The badge's accumulator must already be accrued to now. Leaving is never blocked on yield:
+/// payment is capped at the vault balance and skipped without the yield accounts, and whatever
+/// is not paid is forfeited.
+pub(crate) fn settle_yield<'info>(
+    badge: &mut Account<'info, Badge>,
+    user_badge: &UserBadge,
+    yield_mint: Option<&InterfaceAccount<'info, Mint>>,
+    yield_vault: Option<&InterfaceAccount<'info, TokenAccount>>,
+    user_yield_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&Interface<'info, TokenInterface>>,
+) -> Result<u64> {
+    let pending = badge.release_yield(user_badge);
+    let (Some(yield_mint), Some(yield_vault), Some(user_yield_account), Some(token_program)) =
+        (yield_mint, yield_vault, user_yield_account, token_program)
+    else {
+        return Ok(0);
+    };
+    
+    let amount = pending.min(yield_vault.amount);
+    if amount > 0 {
+        pay_yield(badge, yield_mint, yield_vault, user_yield_account.to_account_info(), token_program, amount)?;
+    }
+    Ok(amount)
+}
\ No newline at end of file
@@ -1,12 +1,7 @@
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
-
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
@@ -16,6 +11,10 @@ use anchor_spl::{
 	required_points: u64,
 	max_earnings: u64,
 	is_active: bool,
+	mint_nft: bool,
+	valid_for_seconds: Option<u64>,
+	tiers: Vec<BadgeTier>,
+	prerequisites: Vec<BadgePrerequisite>,
 )]
 pub struct InitializeBadge<'info> {
 	#[account(
@@ -23,9 +22,19 @@ pub struct InitializeBadge<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
 	#[account(
 		init,
-		space=918,
+		space=Badge::space(&name, &description, &icon_uri, &tiers, &prerequisites),
 		payer=fee_payer,
 		seeds = [
 			b"badge",
@@ -36,6 +45,16 @@ pub struct InitializeBadge<'info> {
 	)]
 	pub badge: Account<'info, Badge>,
 
+	/// CHECK: Record left by closing an earlier badge at this address; only read if one has been created
+	#[account(
+		seeds = [
+			b"closed_badge",
+			badge.key().as_ref(),
+		],
+		bump,
+	)]
+	pub closed_badge: UncheckedAccount<'info>,
+
 	pub owner: Signer<'info>,
 
 	pub system_program: Program<'info, System>,
@@ -45,9 +64,13 @@ pub struct InitializeBadge<'info> {
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` badge: [Badge] 
-/// 2. `[signer]` owner: [AccountInfo] The owner of the badge
-/// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[]` closed_badge: [ClosedBadge] Record left by closing an earlier badge at this address, may be uninitialized
+/// 4. `[signer]` owner: [AccountInfo] The owner of the badge
+/// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 7. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] Unique identifier for this badge
@@ -57,6 +80,10 @@ pub struct InitializeBadge<'info> {
 /// - required_points: [u64] Points required to earn this badge
 /// - max_earnings: [u64] Maximum number of times this badge can be earned
 /// - is_active: [bool] Whether this badge is currently available for earning
+/// - mint_nft: [bool] Whether earning this badge also mints a soulbound NFT to the user
+/// - valid_for_seconds: [Option<u64>] How long the badge stays valid once earned, or None if it never expires
+/// - tiers: [Vec<BadgeTier>] Upgrade levels above the base badge, ordered by increasing required_points
+/// - prerequisites: [Vec<BadgePrerequisite>] Badges the user must already hold to earn this one
 pub fn handler(
 	ctx: Context<InitializeBadge>,
 	badge_id: u64,
@@ -66,6 +93,10 @@ pub fn handler(
 	required_points: u64,
 	max_earnings: u64,
 	is_active: bool,
+	mint_nft: bool,
+	valid_for_seconds: Option<u64>,
+	tiers: Vec<BadgeTier>,
+	prerequisites: Vec<BadgePrerequisite>,
 ) -> Result<()> {
     // Validate inputs
     require!(required_points > 0, BadgeRewardsError::InvalidRequiredPoints);
@@ -73,10 +104,17 @@ pub fn handler(
     require!(name.len() <= 100, BadgeRewardsError::NameTooLong);
     require!(description.len() <= 500, BadgeRewardsError::DescriptionTooLong);
     require!(icon_uri.len() <= 200, BadgeRewardsError::IconUriTooLong);
+    require!(valid_for_seconds != Some(0), BadgeRewardsError::InvalidValidityPeriod);
+    
+    // A badge re-created after being closed keeps counting towards max_earnings from where it left off
+    let total_issued = ClosedBadge::load(&ctx.accounts.closed_badge)?.map_or(0, |closed_badge| closed_badge.total_issued);
     
     // Initialize the badge
     let badge = &mut ctx.accounts.badge;
+    badge.version = BADGE_VERSION;
     badge.owner = ctx.accounts.owner.key();
+    badge.authority = ctx.accounts.owner.key();
+    badge.pending_authority = None;
     badge.badge_id = badge_id;
     badge.name = name;
     badge.description = description;
@@ -84,8 +122,31 @@ pub fn handler(
     badge.required_points = required_points;
     badge.max_earnings = max_earnings;
     badge.total_earned = 0;
+    badge.total_issued = total_issued;
     badge.is_active = is_active;
+    badge.deactivated_at = if is_active { 0 } else { Clock::get()?.unix_timestamp };
+    badge.mint_nft = mint_nft;
+    badge.valid_for_seconds = valid_for_seconds;
+    badge.tiers = tiers;
+    badge.validate_tiers()?;
+    badge.prerequisites = prerequisites;
+    badge.validate_prerequisites()?;
+    badge.emission_rate = 0;
+    badge.yield_mint = None;
+    badge.acc_yield_per_badge = 0;
+    badge.last_yield_update = 0;
+    badge.reserved_yield = 0;
     badge.bump = ctx.bumps.badge;
     
+    emit_cpi!(BadgeCreated {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        name: ctx.accounts.badge.name.clone(),
+        required_points,
+        max_earnings,
+        is_active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
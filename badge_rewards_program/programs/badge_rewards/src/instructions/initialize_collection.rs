@@ -0,0 +1,107 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	collection_id: u64,
+	name: String,
+	badge_ids: Vec<u64>,
+	meta_badge_id: u64,
+)]
+pub struct InitializeCollection<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			meta_badge.owner.as_ref(),
+			meta_badge_id.to_le_bytes().as_ref(),
+		],
+		bump = meta_badge.bump,
+		constraint = meta_badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub meta_badge: Account<'info, Badge>,
+
+	#[account(
+		init,
+		space=Collection::space(&name, &badge_ids),
+		payer=fee_payer,
+		seeds = [
+			b"collection",
+			meta_badge.owner.as_ref(),
+			collection_id.to_le_bytes().as_ref(),
+		],
+		bump,
+	)]
+	pub collection: Account<'info, Collection>,
+
+	pub owner: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+/// Initialize a collection of badges that awards a meta-badge once a user holds them all
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` meta_badge: [Badge] The badge awarded on completion
+/// 3. `[writable]` collection: [Collection] 
+/// 4. `[signer]` owner: [AccountInfo] The owner of the badges
+/// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 7. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - collection_id: [u64] Unique identifier for this collection
+/// - name: [String] Name of the collection
+/// - badge_ids: [Vec<u64>] The owner's badges a user must hold to complete the collection
+/// - meta_badge_id: [u64] The owner's badge awarded on completion
+pub fn handler(
+	ctx: Context<InitializeCollection>,
+	collection_id: u64,
+	name: String,
+	badge_ids: Vec<u64>,
+	meta_badge_id: u64,
+) -> Result<()> {
+    // Validate that the meta-badge exists and the signer is its authority
+    require!(ctx.accounts.meta_badge.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    require!(!ctx.accounts.meta_badge.mint_nft, BadgeRewardsError::MetaBadgeMintsNft);
+    require!(name.len() <= 100, BadgeRewardsError::NameTooLong);
+    
+    // Initialize the collection
+    let collection = &mut ctx.accounts.collection;
+    collection.version = COLLECTION_VERSION;
+    collection.owner = ctx.accounts.meta_badge.owner;
+    collection.collection_id = collection_id;
+    collection.name = name;
+    collection.badge_ids = badge_ids;
+    collection.meta_badge_id = meta_badge_id;
+    collection.total_completed = 0;
+    collection.bump = ctx.bumps.collection;
+    collection.validate_badges()?;
+    
+    emit_cpi!(CollectionCreated {
+        owner: ctx.accounts.collection.owner,
+        collection_id,
+        meta_badge_id,
+        badge_ids: ctx.accounts.collection.badge_ids.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,76 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use crate::program::BadgeRewards;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		init,
+		space=8 + Config::INIT_SPACE,
+		payer=fee_payer,
+		seeds = [
+			b"config",
+		],
+		bump,
+	)]
+	pub config: Account<'info, Config>,
+
+	pub admin: Signer<'info>,
+
+	#[account(
+		constraint = badge_rewards_program.programdata_address()? == Some(program_data.key()) @ BadgeRewardsError::Unauthorized,
+	)]
+	pub badge_rewards_program: Program<'info, BadgeRewards>,
+
+	#[account(
+		constraint = program_data.upgrade_authority_address == Some(admin.key()) @ BadgeRewardsError::Unauthorized,
+	)]
+	pub program_data: Account<'info, ProgramData>,
+
+	pub system_program: Program<'info, System>,
+}
+
+/// Initialize the global program config
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[writable]` config: [Config] 
+/// 2. `[signer]` admin: [AccountInfo] The initial admin, must be the program's upgrade authority
+/// 3. `[]` badge_rewards_program: [AccountInfo] This program, used to locate its program data
+/// 4. `[]` program_data: [ProgramData] This program's upgradeable loader data
+/// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 7. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - protocol_fee_bps: [u16] Protocol fee in basis points
+pub fn handler(
+	ctx: Context<InitializeConfig>,
+	protocol_fee_bps: u16,
+) -> Result<()> {
+    // Validate inputs
+    require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, BadgeRewardsError::InvalidProtocolFee);
+    
+    // Initialize the config
+    let config = &mut ctx.accounts.config;
+    config.version = CONFIG_VERSION;
+    config.admin = ctx.accounts.admin.key();
+    config.paused = false;
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.bump = ctx.bumps.config;
+    
+    emit_cpi!(ConfigInitialized {
+        admin: config.admin,
+        protocol_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -1,12 +1,9 @@
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
@@ -18,6 +15,11 @@ use anchor_spl::{
 	token_mint: Option<Pubkey>,
 	nft_mint: Option<Pubkey>,
 	is_active: bool,
+	min_tier: u8,
+	max_claims: Option<u64>,
+	claim_start: Option<i64>,
+	claim_end: Option<i64>,
+	vesting: Option<VestingSchedule>,
 )]
 pub struct InitializeReward<'info> {
 	#[account(
@@ -25,13 +27,34 @@ pub struct InitializeReward<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
 	#[account(
 		init,
-		space=918,
+		space=Reward::space(&name, &description),
 		payer=fee_payer,
 		seeds = [
 			b"reward",
-			owner.key().as_ref(),
+			badge.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 			reward_id.to_le_bytes().as_ref(),
 		],
@@ -39,18 +62,50 @@ pub struct InitializeReward<'info> {
 	)]
 	pub reward: Account<'info, Reward>,
 
-	pub owner: Signer<'info>,
+	pub authority: Signer<'info>,
+
+	/// The NFT mint, required for NFT rewards
+	pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = authority,
+	)]
+	pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	#[account(
+		init,
+		payer = fee_payer,
+		seeds = [
+			b"reward_vault",
+			reward.key().as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+		token::authority = reward,
+	)]
+	pub reward_vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
 	pub system_program: Program<'info, System>,
+	pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 /// Initialize a new reward for a badge
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` reward: [Reward] 
-/// 2. `[signer]` owner: [AccountInfo] The owner of the reward
-/// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` badge: [Badge] The badge the reward is for
+/// 3. `[writable]` reward: [Reward] 
+/// 4. `[signer]` authority: [AccountInfo] The authority of the badge
+/// 5. `[optional]` reward_mint: [Mint] The NFT mint, required for NFT rewards
+/// 6. `[writable, optional]` authority_token_account: [TokenAccount] The authority's token account holding the NFT
+/// 7. `[writable, optional]` reward_vault: [TokenAccount] Program-owned vault the NFT is escrowed in
+/// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 9. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 10. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 11. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] The badge ID this reward is associated with
@@ -62,6 +117,11 @@ pub struct InitializeReward<'info> {
 /// - token_mint: [Option<Pubkey>] Token mint for token rewards
 /// - nft_mint: [Option<Pubkey>] NFT mint for NFT rewards
 /// - is_active: [bool] Whether this reward is currently available
+/// - min_tier: [u8] Lowest badge tier that may claim this reward, 0 being the base badge
+/// - max_claims: [Option<u64>] How many users may claim this reward, or None if unlimited
+/// - claim_start: [Option<i64>] When claims open, or None to open immediately
+/// - claim_end: [Option<i64>] When claims close, or None to never close
+/// - vesting: [Option<VestingSchedule>] Cliff and duration over which token claims unlock, or None to pay out at once
 pub fn handler(
 	ctx: Context<InitializeReward>,
 	badge_id: u64,
@@ -73,12 +133,22 @@ pub fn handler(
 	token_mint: Option<Pubkey>,
 	nft_mint: Option<Pubkey>,
 	is_active: bool,
+	min_tier: u8,
+	max_claims: Option<u64>,
+	claim_start: Option<i64>,
+	claim_end: Option<i64>,
+	vesting: Option<VestingSchedule>,
 ) -> Result<()> {
+    // Validate that the signer is the badge's authority; the reward stays keyed by the badge owner
+    require!(ctx.accounts.badge.authority == ctx.accounts.authority.key(), BadgeRewardsError::Unauthorized);
+    
     // Validate inputs
     require!(reward_type <= 2, BadgeRewardsError::InvalidRewardType);
     require!(reward_value > 0, BadgeRewardsError::InvalidRewardValue);
     require!(name.len() <= 100, BadgeRewardsError::NameTooLong);
     require!(description.len() <= 500, BadgeRewardsError::DescriptionTooLong);
+    require!(min_tier as usize <= ctx.accounts.badge.tiers.len(), BadgeRewardsError::InvalidMinTier);
+    require!(max_claims != Some(0), BadgeRewardsError::InvalidMaxClaims);
     
     // For token rewards, token_mint must be provided
     if reward_type == 0 {
@@ -90,8 +160,32 @@ pub fn handler(
         require!(nft_mint.is_some(), BadgeRewardsError::MissingNftMint);
     }
     
+    // Escrow the NFT in the reward vault
+    if reward_type == 1 {
+        let reward_mint = ctx.accounts.reward_mint.as_ref().ok_or(BadgeRewardsError::MissingNftMint)?;
+        require!(Some(reward_mint.key()) == nft_mint, BadgeRewardsError::InvalidNftMint);
+        require!(reward_mint.decimals == 0 && reward_mint.supply == 1, BadgeRewardsError::InvalidNftMint);
+        
+        let authority_token_account = ctx.accounts.authority_token_account.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+        let reward_vault = ctx.accounts.reward_vault.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingRewardVault)?;
+        
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            from: authority_token_account.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: reward_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, 1, reward_mint.decimals)?;
+    }
+    
     // Initialize the reward
     let reward = &mut ctx.accounts.reward;
+    reward.version = REWARD_VERSION;
+    reward.owner = ctx.accounts.badge.owner;
+    reward.authority = ctx.accounts.authority.key();
+    reward.pending_authority = None;
     reward.badge_id = badge_id;
     reward.reward_id = reward_id;
     reward.name = name;
@@ -101,7 +195,31 @@ pub fn handler(
     reward.token_mint = token_mint;
     reward.nft_mint = nft_mint;
     reward.is_active = is_active;
+    reward.stake_status = StakeStatus::Available;
+    reward.staked_by = Pubkey::default();
+    reward.staked_at = 0;
+    reward.min_stake_period = 0;
+    reward.can_unstake_at = 0;
     reward.bump = ctx.bumps.reward;
+    reward.min_tier = min_tier;
+    reward.max_claims = max_claims;
+    reward.total_claimed = 0;
+    reward.claim_start = claim_start;
+    reward.claim_end = claim_end;
+    reward.validate_claim_window()?;
+    reward.vesting = vesting;
+    reward.validate_vesting()?;
+    reward.vesting_ends_at = 0;
+    
+    emit_cpi!(RewardCreated {
+        owner: ctx.accounts.reward.owner,
+        badge_id,
+        reward_id,
+        reward_type,
+        reward_value,
+        is_active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     
     Ok(())
 }
\ No newline at end of file
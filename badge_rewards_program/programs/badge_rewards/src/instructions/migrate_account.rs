@@ -0,0 +1,688 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	/// CHECK: Global program config, decoded in any layout version so an outdated config can itself be migrated
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump,
+	)]
+	pub config: UncheckedAccount<'info>,
+
+	/// CHECK: Any account owned by this program; its type is read from the discriminator
+	#[account(
+		mut,
+		owner = crate::ID @ BadgeRewardsError::InvalidAccountLayout,
+	)]
+	pub account: UncheckedAccount<'info>,
+
+	pub system_program: Program<'info, System>,
+}
+
+/// Upgrade an account to the current layout of its type (permissionless)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] Pays for any extra rent the new layout needs
+/// 1. `[]` config: [Config] Global program config in any layout version, rejected while paused
+/// 2. `[writable]` account: [AccountInfo] The Badge, Reward, UserBadge, UserReward, UserPoints, RevokedBadge, Config, Collection or VestingEscrow to migrate
+/// 3. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+pub fn handler(
+	ctx: Context<MigrateAccount>,
+) -> Result<()> {
+    // Validate that the program is not paused
+    let config = Config::load_any_version(&ctx.accounts.config)?;
+    require!(!config.paused, BadgeRewardsError::ProgramPaused);
+    
+    let info = ctx.accounts.account.to_account_info();
+    let key = info.key();
+    let data = info.try_borrow_data()?.to_vec();
+    require!(data.len() > 8, BadgeRewardsError::InvalidAccountLayout);
+    
+    // Decode the account into its current layout and re-encode it
+    let discriminator = &data[..8];
+    let (from_version, to_version, space, encoded) = if discriminator == Badge::DISCRIMINATOR {
+        upgrade::<Badge>(&key, &data)?
+    } else if discriminator == Reward::DISCRIMINATOR {
+        upgrade::<Reward>(&key, &data)?
+    } else if discriminator == UserBadge::DISCRIMINATOR {
+        upgrade::<UserBadge>(&key, &data)?
+    } else if discriminator == UserReward::DISCRIMINATOR {
+        upgrade::<UserReward>(&key, &data)?
+    } else if discriminator == UserPoints::DISCRIMINATOR {
+        upgrade::<UserPoints>(&key, &data)?
+    } else if discriminator == RevokedBadge::DISCRIMINATOR {
+        upgrade::<RevokedBadge>(&key, &data)?
+    } else if discriminator == Config::DISCRIMINATOR {
+        upgrade::<Config>(&key, &data)?
+    } else if discriminator == Collection::DISCRIMINATOR {
+        upgrade::<Collection>(&key, &data)?
+    } else if discriminator == VestingEscrow::DISCRIMINATOR {
+        upgrade::<VestingEscrow>(&key, &data)?
+    } else {
+        return err!(BadgeRewardsError::InvalidAccountLayout);
+    };
+    
+    // Grow the account if needed, topping up rent from the fee payer; it is never shrunk
+    let space = space.max(data.len());
+    let rent = Rent::get()?.minimum_balance(space);
+    if info.lamports() < rent {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.fee_payer.to_account_info(),
+            to: info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, rent - info.lamports())?;
+    }
+    info.resize(space)?;
+    
+    // Write the new layout over zeroed data
+    let mut account_data = info.try_borrow_mut_data()?;
+    account_data.fill(0);
+    account_data[..encoded.len()].copy_from_slice(&encoded);
+    drop(account_data);
+    
+    emit_cpi!(AccountMigrated {
+        account: key,
+        from_version,
+        to_version,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// An account type that migrate_account can upgrade
+trait Migrate: AccountSerialize + AccountDeserialize + Discriminator + Space {
+    const VERSION: u8;
+    
+    fn set_version(&mut self, version: u8);
+    
+    /// The PDA derived from the account's own stored seeds
+    fn address(&self) -> Option<Pubkey>;
+    
+    /// Account size in the current layout
+    fn space(&self) -> usize;
+    
+    /// Decode `data`, without its discriminator, in the layout of the original unversioned
+    /// program, if the type existed then
+    fn from_baseline(_data: &[u8]) -> Option<Self> {
+        None
+    }
+    
+    /// Fill in fields added after `from_version` that cannot be left zeroed
+    fn fill_new_fields(&mut self, _from_version: u8) {}
+}
+
+/// Decode `data` in whichever layout it was written with and re-encode it in the current one.
+/// Returns the old and new versions, the required space and the encoded account.
+///
+/// Version 0 is the original unversioned program, decoded through `Migrate::from_baseline`;
+/// version 1 inserted the version byte right after the discriminator. Fields added since
+/// version 1 are appended to the end of the struct, so an older versioned layout decodes with
+/// them zeroed (None, 0, false or empty). Every candidate decoding must re-derive the account's
+/// own address, so a misread layout is rejected.
+fn upgrade<T: Migrate>(key: &Pubkey, data: &[u8]) -> Result<(u8, u8, usize, Vec<u8>)> {
+    let decode = |bytes: &[u8]| -> Option<T> {
+        let mut padded = bytes.to_vec();
+        padded.resize(bytes.len() + T::INIT_SPACE, 0);
+        T::try_deserialize(&mut &padded[..])
+            .ok()
+            .filter(|account| account.address() == Some(*key))
+    };
+    
+    let stored_version = data[8];
+    let (from_version, mut account) = match decode(data) {
+        // Accounts already on the current layout are left alone
+        Some(_) if stored_version == T::VERSION => return err!(BadgeRewardsError::AccountAlreadyMigrated),
+        Some(account) if stored_version >= 1 && stored_version < T::VERSION => (stored_version, account),
+        _ => {
+            let account = T::from_baseline(&data[8..])
+                .filter(|account| account.address() == Some(*key))
+                .ok_or(BadgeRewardsError::InvalidAccountLayout)?;
+            (0, account)
+        }
+    };
+    account.fill_new_fields(from_version);
+    account.set_version(T::VERSION);
+    
+    let mut encoded = Vec::with_capacity(account.space());
+    account.try_serialize(&mut encoded)?;
+    Ok((from_version, T::VERSION, account.space(), encoded))
+}
+
+fn pda(seeds: &[&[u8]]) -> Option<Pubkey> {
+    Pubkey::create_program_address(seeds, &crate::ID).ok()
+}
+
+impl Migrate for Badge {
+    const VERSION: u8 = BADGE_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"badge", self.owner.as_ref(), &self.badge_id.to_le_bytes(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        Badge::space(&self.name, &self.description, &self.icon_uri, &self.tiers, &self.prerequisites)
+    }
+    
+    fn from_baseline(data: &[u8]) -> Option<Self> {
+        let badge = baseline::Badge::deserialize(&mut &data[..]).ok()?;
+        Some(Badge {
+            version: 0,
+            owner: badge.owner,
+            authority: badge.owner,
+            pending_authority: None,
+            badge_id: badge.badge_id,
+            name: badge.name,
+            description: badge.description,
+            icon_uri: badge.icon_uri,
+            required_points: badge.required_points,
+            max_earnings: badge.max_earnings,
+            total_earned: badge.total_earned,
+            // Past issues were not counted; the current holders are the best lower bound
+            total_issued: badge.total_earned,
+            is_active: badge.is_active,
+            deactivated_at: 0,
+            mint_nft: false,
+            bump: badge.bump,
+            valid_for_seconds: None,
+            tiers: vec![],
+            prerequisites: vec![],
+            emission_rate: 0,
+            yield_mint: None,
+            acc_yield_per_badge: 0,
+            last_yield_update: 0,
+            reserved_yield: 0,
+        })
+    }
+    
+    fn fill_new_fields(&mut self, from_version: u8) {
+        // Holders' unpaid yield was not tracked, so reserve every holder's share since yield began
+        if from_version < 6 {
+            let reserved = self.acc_yield_per_badge.saturating_mul(self.total_earned as u128) / YIELD_PRECISION;
+            self.reserved_yield = u64::try_from(reserved).unwrap_or(u64::MAX);
+        }
+    }
+}
+
+impl Migrate for Reward {
+    const VERSION: u8 = REWARD_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"reward", self.owner.as_ref(), &self.badge_id.to_le_bytes(), &self.reward_id.to_le_bytes(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        Reward::space(&self.name, &self.description)
+    }
+    
+    fn from_baseline(data: &[u8]) -> Option<Self> {
+        let reward = baseline::Reward::deserialize(&mut &data[..]).ok()?;
+        Some(Reward {
+            version: 0,
+            owner: reward.owner,
+            authority: reward.owner,
+            pending_authority: None,
+            badge_id: reward.badge_id,
+            reward_id: reward.reward_id,
+            name: reward.name,
+            description: reward.description,
+            reward_type: reward.reward_type,
+            reward_value: reward.reward_value,
+            token_mint: reward.token_mint,
+            nft_mint: reward.nft_mint,
+            is_active: reward.is_active,
+            stake_status: StakeStatus::Available,
+            staked_by: Pubkey::default(),
+            staked_at: 0,
+            min_stake_period: 0,
+            can_unstake_at: 0,
+            bump: reward.bump,
+            min_tier: 0,
+            max_claims: None,
+            total_claimed: 0,
+            claim_start: None,
+            claim_end: None,
+            vesting: None,
+            vesting_ends_at: 0,
+        })
+    }
+}
+
+impl Migrate for UserBadge {
+    const VERSION: u8 = USER_BADGE_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"user_badge", self.user.as_ref(), &self.badge_id.to_le_bytes(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        8 + UserBadge::INIT_SPACE
+    }
+}
+
+impl Migrate for UserReward {
+    const VERSION: u8 = USER_REWARD_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"user_reward", self.user.as_ref(), &self.reward_id.to_le_bytes(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        8 + UserReward::INIT_SPACE
+    }
+}
+
+impl Migrate for UserPoints {
+    const VERSION: u8 = USER_POINTS_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"user_points", self.owner.as_ref(), self.user.as_ref(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        8 + UserPoints::INIT_SPACE
+    }
+}
+
+impl Migrate for RevokedBadge {
+    const VERSION: u8 = REVOKED_BADGE_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        let (badge, _) = Pubkey::find_program_address(&[b"badge", self.badge_owner.as_ref(), &self.badge_id.to_le_bytes()], &crate::ID);
+        pda(&[b"revoked_badge", badge.as_ref(), self.user.as_ref(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        8 + RevokedBadge::INIT_SPACE
+    }
+}
+
+impl Migrate for Config {
+    const VERSION: u8 = CONFIG_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"config", &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        8 + Config::INIT_SPACE
+    }
+}
+
+impl Migrate for Collection {
+    const VERSION: u8 = COLLECTION_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"collection", self.owner.as_ref(), &self.collection_id.to_le_bytes(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        Collection::space(&self.name, &self.badge_ids)
+    }
+}
+
+impl Migrate for VestingEscrow {
+    const VERSION: u8 = VESTING_ESCROW_VERSION;
+    
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    
+    fn address(&self) -> Option<Pubkey> {
+        pda(&[b"vesting_escrow", self.reward.as_ref(), self.user.as_ref(), &[self.bump]])
+    }
+    
+    fn space(&self) -> usize {
+        8 + VestingEscrow::INIT_SPACE
+    }
+}
+
+/// Layouts written by the original unversioned program. Its user badges and user rewards were
+/// allocated 40 bytes, too few for their own layout, so none could be written and only badges
+/// and rewards need decoding
+mod baseline {
+    use anchor_lang::prelude::*;
+    
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct Badge {
+        pub owner: Pubkey,
+        pub badge_id: u64,
+        pub name: String,
+        pub description: String,
+        pub icon_uri: String,
+        pub required_points: u64,
+        pub max_earnings: u64,
+        pub total_earned: u64,
+        pub is_active: bool,
+        pub bump: u8,
+    }
+    
+    #[derive(AnchorSerialize, AnchorDeserialize)]
+    pub struct Reward {
+        pub owner: Pubkey,
+        pub badge_id: u64,
+        pub reward_id: u64,
+        pub name: String,
+        pub description: String,
+        pub reward_type: u8,
+        pub reward_value: u64,
+        pub token_mint: Option<Pubkey>,
+        pub nft_mint: Option<Pubkey>,
+        pub is_active: bool,
+        pub bump: u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Borsh-encode each value in turn, as an account body
+    macro_rules! encode {
+        ($($value:expr),* $(,)?) => {{
+            let mut body = Vec::new();
+            $(AnchorSerialize::serialize(&$value, &mut body).unwrap();)*
+            body
+        }};
+    }
+    
+    /// Account data holding `body` under `T`'s discriminator, zero-padded to `len` like spare allocated space
+    fn account_data<T: Discriminator>(body: &[u8], len: usize) -> Vec<u8> {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(body);
+        data.resize(data.len().max(len), 0);
+        data
+    }
+    
+    fn migrate<T: Migrate>(key: &Pubkey, data: &[u8]) -> (u8, T) {
+        let (from_version, to_version, space, encoded) = upgrade::<T>(key, data).unwrap();
+        assert_eq!(to_version, T::VERSION);
+        assert!(encoded.len() <= space);
+        (from_version, T::try_deserialize(&mut &encoded[..]).unwrap())
+    }
+    
+    fn address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, &crate::ID)
+    }
+    
+    /// A Badge as written by layout `version`, 1 through 5
+    fn versioned_badge(version: u8, owner: &Pubkey, badge_id: u64, bump: u8) -> Vec<u8> {
+        let mut body = encode!(
+            version,
+            *owner,
+            *owner,
+            None::<Pubkey>,
+            badge_id,
+            "Early adopter".to_string(),
+            "Joined in the first week".to_string(),
+            "https://example.com/badge.png".to_string(),
+            100u64,
+            1_000u64,
+            42u64,
+            50u64,
+            true,
+            0i64,
+            false,
+            bump,
+        );
+        if version >= 2 {
+            body.extend(encode!(Some(86_400u64)));
+        }
+        if version >= 3 {
+            body.extend(encode!(vec![BadgeTier { required_points: 500, icon_uri: "https://example.com/gold.png".to_string() }]));
+        }
+        if version >= 4 {
+            body.extend(encode!(Vec::<BadgePrerequisite>::new()));
+        }
+        if version >= 5 {
+            body.extend(encode!(10u64, Some(*owner), 3 * YIELD_PRECISION, 1_700_000_000i64));
+        }
+        body
+    }
+    
+    /// A Reward as written by layout `version`, 1 through 4
+    fn versioned_reward(version: u8, owner: &Pubkey, badge_id: u64, reward_id: u64, bump: u8) -> Vec<u8> {
+        let mut body = encode!(
+            version,
+            *owner,
+            *owner,
+            None::<Pubkey>,
+            badge_id,
+            reward_id,
+            "Free coffee".to_string(),
+            "One coffee at any store".to_string(),
+            2u8,
+            1u64,
+            None::<Pubkey>,
+            None::<Pubkey>,
+            true,
+            StakeStatus::Available,
+            Pubkey::default(),
+            0i64,
+            0u64,
+            0i64,
+            bump,
+        );
+        if version >= 2 {
+            body.extend(encode!(1u8));
+        }
+        if version >= 3 {
+            body.extend(encode!(Some(10u64), 3u64));
+        }
+        if version >= 4 {
+            body.extend(encode!(Some(1_000i64), None::<i64>));
+        }
+        body
+    }
+    
+    /// A UserBadge as written by layout `version`, 1 through 4
+    fn versioned_user_badge(version: u8, user: &Pubkey, badge_id: u64, bump: u8, badge: &Pubkey) -> Vec<u8> {
+        let mut body = encode!(version, *user, badge_id, 1_700_000_000i64, *user, None::<Pubkey>, bump);
+        if version >= 2 {
+            body.extend(encode!(Some(1_800_000_000i64)));
+        }
+        if version >= 3 {
+            body.extend(encode!(1u8));
+        }
+        if version >= 4 {
+            body.extend(encode!(*badge));
+        }
+        body
+    }
+    
+    #[test]
+    fn migrates_baseline_badge() {
+        let owner = Pubkey::new_unique();
+        let (key, bump) = address(&[b"badge", owner.as_ref(), &7u64.to_le_bytes()]);
+        let body = encode!(baseline::Badge {
+            owner,
+            badge_id: 7,
+            name: "Early adopter".to_string(),
+            description: "Joined in the first week".to_string(),
+            icon_uri: "https://example.com/badge.png".to_string(),
+            required_points: 100,
+            max_earnings: 1_000,
+            total_earned: 42,
+            is_active: true,
+            bump,
+        });
+        
+        // The baseline program allocated every badge with space=918
+        let (from_version, badge) = migrate::<Badge>(&key, &account_data::<Badge>(&body, 918));
+        assert_eq!(from_version, 0);
+        assert_eq!(badge.version, BADGE_VERSION);
+        assert_eq!(badge.owner, owner);
+        assert_eq!(badge.authority, owner);
+        assert_eq!(badge.badge_id, 7);
+        assert_eq!(badge.name, "Early adopter");
+        assert_eq!(badge.icon_uri, "https://example.com/badge.png");
+        assert_eq!(badge.max_earnings, 1_000);
+        assert_eq!(badge.total_earned, 42);
+        assert_eq!(badge.total_issued, 42);
+        assert!(badge.is_active);
+        assert_eq!(badge.bump, bump);
+        assert!(badge.tiers.is_empty());
+    }
+    
+    #[test]
+    fn migrates_baseline_reward() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let (key, bump) = address(&[b"reward", owner.as_ref(), &7u64.to_le_bytes(), &3u64.to_le_bytes()]);
+        let body = encode!(baseline::Reward {
+            owner,
+            badge_id: 7,
+            reward_id: 3,
+            name: "Airdrop".to_string(),
+            description: "Tokens for early adopters".to_string(),
+            reward_type: 0,
+            reward_value: 5_000,
+            token_mint: Some(mint),
+            nft_mint: None,
+            is_active: true,
+            bump,
+        });
+        
+        let (from_version, reward) = migrate::<Reward>(&key, &account_data::<Reward>(&body, 918));
+        assert_eq!(from_version, 0);
+        assert_eq!(reward.version, REWARD_VERSION);
+        assert_eq!(reward.owner, owner);
+        assert_eq!(reward.authority, owner);
+        assert_eq!(reward.reward_id, 3);
+        assert_eq!(reward.description, "Tokens for early adopters");
+        assert_eq!(reward.reward_value, 5_000);
+        assert_eq!(reward.token_mint, Some(mint));
+        assert!(reward.stake_status == StakeStatus::Available);
+        assert_eq!(reward.max_claims, None);
+        assert_eq!(reward.bump, bump);
+    }
+    
+    #[test]
+    fn migrates_versioned_badges() {
+        let owner = Pubkey::new_unique();
+        let (key, bump) = address(&[b"badge", owner.as_ref(), &7u64.to_le_bytes()]);
+        for version in 1..=5 {
+            let data = account_data::<Badge>(&versioned_badge(version, &owner, 7, bump), 0);
+            let (from_version, badge) = migrate::<Badge>(&key, &data);
+            assert_eq!(from_version, version);
+            assert_eq!(badge.version, BADGE_VERSION);
+            assert_eq!(badge.owner, owner);
+            assert_eq!(badge.description, "Joined in the first week");
+            assert_eq!(badge.total_issued, 50);
+            assert_eq!(badge.bump, bump);
+            assert_eq!(badge.valid_for_seconds, (version >= 2).then_some(86_400));
+            assert_eq!(badge.tiers.len(), if version >= 3 { 1 } else { 0 });
+            assert!(badge.prerequisites.is_empty());
+            assert_eq!(badge.emission_rate, if version >= 5 { 10 } else { 0 });
+            assert_eq!(badge.yield_mint, (version >= 5).then_some(owner));
+            // Every holder's share of the yield accrued before reserves were tracked
+            assert_eq!(badge.reserved_yield, if version >= 5 { 3 * 42 } else { 0 });
+        }
+    }
+    
+    #[test]
+    fn migrates_versioned_rewards() {
+        let owner = Pubkey::new_unique();
+        let (key, bump) = address(&[b"reward", owner.as_ref(), &7u64.to_le_bytes(), &3u64.to_le_bytes()]);
+        for version in 1..=4 {
+            let data = account_data::<Reward>(&versioned_reward(version, &owner, 7, 3, bump), 0);
+            let (from_version, reward) = migrate::<Reward>(&key, &data);
+            assert_eq!(from_version, version);
+            assert_eq!(reward.version, REWARD_VERSION);
+            assert_eq!(reward.name, "Free coffee");
+            assert_eq!(reward.bump, bump);
+            assert_eq!(reward.min_tier, if version >= 2 { 1 } else { 0 });
+            assert_eq!(reward.max_claims, (version >= 3).then_some(10));
+            assert_eq!(reward.claim_start, (version >= 4).then_some(1_000));
+            assert!(reward.vesting.is_none());
+            assert_eq!(reward.vesting_ends_at, 0);
+        }
+    }
+    
+    #[test]
+    fn migrates_versioned_user_badges() {
+        let user = Pubkey::new_unique();
+        let badge = Pubkey::new_unique();
+        let (key, bump) = address(&[b"user_badge", user.as_ref(), &7u64.to_le_bytes()]);
+        for version in 1..=4 {
+            let data = account_data::<UserBadge>(&versioned_user_badge(version, &user, 7, bump, &badge), 0);
+            let (from_version, user_badge) = migrate::<UserBadge>(&key, &data);
+            assert_eq!(from_version, version);
+            assert_eq!(user_badge.version, USER_BADGE_VERSION);
+            assert_eq!(user_badge.rent_payer, user);
+            assert_eq!(user_badge.expires_at, (version >= 2).then_some(1_800_000_000));
+            assert_eq!(user_badge.tier, if version >= 3 { 1 } else { 0 });
+            assert_eq!(user_badge.badge, if version >= 4 { badge } else { Pubkey::default() });
+            assert_eq!(user_badge.yield_debt, 0);
+        }
+    }
+    
+    #[test]
+    fn rejects_current_and_misplaced_accounts() {
+        let owner = Pubkey::new_unique();
+        let (key, bump) = address(&[b"badge", owner.as_ref(), &7u64.to_le_bytes()]);
+        let data = account_data::<Badge>(&versioned_badge(4, &owner, 7, bump), 0);
+        let (_, mut badge) = migrate::<Badge>(&key, &data);
+        
+        // An account already on the current layout
+        let mut current = Vec::new();
+        badge.try_serialize(&mut current).unwrap();
+        assert_eq!(upgrade::<Badge>(&key, &current).unwrap_err(), BadgeRewardsError::AccountAlreadyMigrated.into());
+        
+        // A layout decoded at an address its seeds do not derive
+        assert_eq!(upgrade::<Badge>(&Pubkey::new_unique(), &data).unwrap_err(), BadgeRewardsError::InvalidAccountLayout.into());
+        
+        // A version newer than this program knows
+        badge.version = BADGE_VERSION + 1;
+        let mut newer = Vec::new();
+        badge.try_serialize(&mut newer).unwrap();
+        assert_eq!(upgrade::<Badge>(&key, &newer).unwrap_err(), BadgeRewardsError::InvalidAccountLayout.into());
+    }
+}
\ No newline at end of file
@@ -1,3 +1,5 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_badge;
 pub mod update_badge;
 pub mod initialize_reward;
@@ -9,6 +11,37 @@ pub mod stake_badge;
 pub mod unstake_badge;
 pub mod freeze_badge;
 pub mod revoke_badge;
+pub mod award_points;
+pub mod deduct_points;
+pub mod fund_reward;
+pub mod withdraw_reward;
+pub mod stake_reward;
+pub mod unstake_reward;
+pub mod stake_reward_player;
+pub mod unstake_reward_player;
+pub mod initialize_config;
+pub mod set_pause;
+pub mod transfer_admin;
+pub mod set_protocol_fee;
+pub mod propose_badge_owner;
+pub mod accept_badge_owner;
+pub mod propose_reward_owner;
+pub mod accept_reward_owner;
+pub mod close_badge;
+pub mod close_reward;
+pub mod migrate_account;
+pub mod backfill_user_badge;
+pub mod renew_badge;
+pub mod upgrade_badge;
+pub mod initialize_collection;
+pub mod complete_collection;
+pub mod sweep_expired_reward;
+pub mod withdraw_vested;
+pub mod reclaim_unvested;
+pub mod set_badge_yield;
+pub mod fund_badge_yield;
+pub mod harvest;
+pub mod withdraw_badge_yield;
 
 pub use initialize_badge::*;
 pub use update_badge::*;
@@ -20,4 +53,35 @@ pub use get_user_rewards::*;
 pub use stake_badge::*;
 pub use unstake_badge::*;
 pub use freeze_badge::*;
-pub use revoke_badge::*;
\ No newline at end of file
+pub use revoke_badge::*;
+pub use award_points::*;
+pub use deduct_points::*;
+pub use fund_reward::*;
+pub use withdraw_reward::*;
+pub use stake_reward::*;
+pub use unstake_reward::*;
+pub use stake_reward_player::*;
+pub use unstake_reward_player::*;
+pub use initialize_config::*;
+pub use set_pause::*;
+pub use transfer_admin::*;
+pub use set_protocol_fee::*;
+pub use propose_badge_owner::*;
+pub use accept_badge_owner::*;
+pub use propose_reward_owner::*;
+pub use accept_reward_owner::*;
+pub use close_badge::*;
+pub use close_reward::*;
+pub use migrate_account::*;
+pub use backfill_user_badge::*;
+pub use renew_badge::*;
+pub use upgrade_badge::*;
+pub use initialize_collection::*;
+pub use complete_collection::*;
+pub use sweep_expired_reward::*;
+pub use withdraw_vested::*;
+pub use reclaim_unvested::*;
+pub use set_badge_yield::*;
+pub use fund_badge_yield::*;
+pub use harvest::*;
+pub use withdraw_badge_yield::*;
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct ProposeBadgeOwner<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	pub authority: Signer<'info>,
+}
+
+/// Propose a new owner for a badge, or cancel a pending proposal
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[signer]` authority: [AccountInfo] The current authority of the badge
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to transfer
+/// - new_authority: [Option<Pubkey>] The proposed owner, or None to cancel the proposal
+pub fn handler(
+	ctx: Context<ProposeBadgeOwner>,
+	badge_id: u64,
+	new_authority: Option<Pubkey>,
+) -> Result<()> {
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.authority.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    
+    // Record the proposal; it takes effect once the new owner accepts
+    ctx.accounts.badge.pending_authority = new_authority;
+    
+    emit_cpi!(BadgeOwnerProposed {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        authority: ctx.accounts.badge.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct ProposeRewardOwner<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	pub authority: Signer<'info>,
+}
+
+/// Propose a new owner for a reward, or cancel a pending proposal
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` reward: [Reward] 
+/// 3. `[signer]` authority: [AccountInfo] The current authority of the reward
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward belongs to
+/// - reward_id: [u64] The reward ID to transfer
+/// - new_authority: [Option<Pubkey>] The proposed owner, or None to cancel the proposal
+pub fn handler(
+	ctx: Context<ProposeRewardOwner>,
+	badge_id: u64,
+	reward_id: u64,
+	new_authority: Option<Pubkey>,
+) -> Result<()> {
+    // Validate that the reward exists and the signer is its authority
+    require!(ctx.accounts.reward.authority == ctx.accounts.authority.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    
+    // Record the proposal; it takes effect once the new owner accepts
+    ctx.accounts.reward.pending_authority = new_authority;
+    
+    emit_cpi!(RewardOwnerProposed {
+        owner: ctx.accounts.reward.owner,
+        badge_id,
+        reward_id,
+        authority: ctx.accounts.reward.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
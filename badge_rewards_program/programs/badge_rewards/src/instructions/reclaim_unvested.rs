@@ -0,0 +1,143 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReclaimUnvested<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"vesting_escrow",
+			vesting_escrow.reward.as_ref(),
+			vesting_escrow.user.as_ref(),
+		],
+		bump = vesting_escrow.bump,
+		constraint = vesting_escrow.version == VESTING_ESCROW_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub vesting_escrow: Account<'info, VestingEscrow>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"vesting_vault",
+			vesting_escrow.reward.as_ref(),
+			vesting_escrow.user.as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+		token::authority = vesting_escrow,
+	)]
+	pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+	/// CHECK: Revocation record for the user's badge the reward was claimed with; must have been created
+	#[account(
+		seeds = [
+			b"revoked_badge",
+			vesting_escrow.badge.as_ref(),
+			vesting_escrow.user.as_ref(),
+		],
+		bump,
+	)]
+	pub revoked_badge: UncheckedAccount<'info>,
+
+	/// The token mint held by the vesting vault
+	#[account(
+		address = vesting_escrow.mint @ BadgeRewardsError::InvalidTokenMint,
+	)]
+	pub reward_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward_vault",
+			vesting_escrow.reward.as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+	)]
+	pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Return the unvested part of a vesting token reward to the reward vault after the badge was revoked (permissionless)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` vesting_escrow: [VestingEscrow] 
+/// 3. `[writable]` vesting_vault: [TokenAccount] Token account holding the escrowed tokens
+/// 4. `[]` revoked_badge: [RevokedBadge] Revocation record for the user's badge the reward was claimed with
+/// 5. `[]` reward_mint: [Mint] The token mint held by the vesting vault
+/// 6. `[writable]` reward_vault: [TokenAccount] The vault of the reward the tokens were claimed from
+/// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 9. `[]` program: [AccountInfo] Auto-generated, this program
+pub fn handler(
+	ctx: Context<ReclaimUnvested>,
+) -> Result<()> {
+    // Validate that the badge was revoked after the reward was claimed
+    let revoked_badge = RevokedBadge::load(&ctx.accounts.revoked_badge)?.ok_or(BadgeRewardsError::BadgeNotRevoked)?;
+    require!(revoked_badge.revoked_at >= ctx.accounts.vesting_escrow.start_at, BadgeRewardsError::BadgeNotRevoked);
+    
+    // Everything not unlocked by the revocation goes back
+    let vesting_escrow = &ctx.accounts.vesting_escrow;
+    let vested_amount = vesting_escrow.vested_amount(revoked_badge.revoked_at);
+    require!(vesting_escrow.total_amount > vested_amount, BadgeRewardsError::NothingToReclaim);
+    
+    // Keep enough, grossed up for any Token-2022 transfer fee, to pay out what vested but was not yet withdrawn
+    let unwithdrawn_amount = vested_amount.saturating_sub(vesting_escrow.withdrawn_amount);
+    let reserved_amount = pre_fee_amount(&ctx.accounts.reward_mint.to_account_info(), unwithdrawn_amount)?;
+    let amount = ctx.accounts.vesting_vault.amount.saturating_sub(reserved_amount);
+    require!(amount > 0, BadgeRewardsError::NothingToReclaim);
+    
+    // Transfer the unvested tokens back to the reward vault, signed by the vesting escrow PDA
+    let reward_key = vesting_escrow.reward;
+    let user_key = vesting_escrow.user;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vesting_escrow",
+        reward_key.as_ref(),
+        user_key.as_ref(),
+        &[vesting_escrow.bump],
+    ]];
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: ctx.accounts.vesting_vault.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: ctx.accounts.vesting_escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+    
+    // Truncate the schedule at the revocation so only the vested amount remains withdrawable
+    ctx.accounts.vesting_escrow.total_amount = vested_amount;
+    ctx.accounts.vesting_escrow.end_at = revoked_badge.revoked_at;
+    
+    emit_cpi!(UnvestedReclaimed {
+        user: user_key,
+        reward: reward_key,
+        amount,
+        vested_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	user: Pubkey,
+)]
+pub struct RenewBadge<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_badge",
+			user.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = user_badge.bump,
+		constraint = user_badge.version == USER_BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_badge: Account<'info, UserBadge>,
+
+	pub badge_owner: Signer<'info>,
+}
+
+/// Extend a user's badge validity without re-issuing it
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` badge: [Badge] 
+/// 3. `[writable]` user_badge: [UserBadge] 
+/// 4. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+/// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 6. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to renew
+/// - user: [Pubkey] The user holding the badge
+pub fn handler(
+	ctx: Context<RenewBadge>,
+	badge_id: u64,
+	user: Pubkey,
+) -> Result<()> {
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    
+    // Validate that the user has the badge
+    require!(ctx.accounts.user_badge.user == user, BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.badge == ctx.accounts.badge.key(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Extend by a full validity period from now, or from the current expiry if it is still ahead
+    let now = Clock::get()?.unix_timestamp;
+    let renew_from = ctx.accounts.user_badge.expires_at.map_or(now, |expires_at| expires_at.max(now));
+    let expires_at = ctx.accounts.badge.expires_at(renew_from).ok_or(BadgeRewardsError::BadgeDoesNotExpire)?;
+    ctx.accounts.user_badge.expires_at = Some(expires_at);
+    
+    emit_cpi!(BadgeRenewed {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user,
+        expires_at,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
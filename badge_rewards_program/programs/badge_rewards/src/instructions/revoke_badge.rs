@@ -1,9 +1,15 @@
 use crate::*;
 use anchor_lang::prelude::*;
 
+use anchor_spl::token_interface::{burn, Burn, Mint, Token2022, TokenAccount};
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
+	reason_code: u8,
+	evidence_uri: Option<String>,
+	allow_reissue: bool,
 )]
 pub struct RevokeBadge<'info> {
 	#[account(
@@ -11,66 +17,191 @@ pub struct RevokeBadge<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
 	#[account(
 		mut,
 		seeds = [
 			b"badge",
-			badge_owner.key().as_ref(),
+			badge.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub badge: Account<'info, Badge>,
 
 	#[account(
 		mut,
+		close = rent_recipient,
 		seeds = [
 			b"user_badge",
 			user.key().as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = user_badge.bump,
+		constraint = user_badge.version == USER_BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub user_badge: Account<'info, UserBadge>,
 
+	#[account(
+		init_if_needed,
+		space=8 + RevokedBadge::INIT_SPACE,
+		payer=fee_payer,
+		seeds = [
+			b"revoked_badge",
+			badge.key().as_ref(),
+			user.key().as_ref(),
+		],
+		bump,
+	)]
+	pub revoked_badge: Account<'info, RevokedBadge>,
+
 	pub badge_owner: Signer<'info>,
-	pub user: Signer<'info>,
+	/// CHECK: The user losing the badge; only used to derive the user_badge PDA
+	pub user: UncheckedAccount<'info>,
+
+	#[account(
+		mut,
+		address = user_badge.rent_payer @ BadgeRewardsError::InvalidRentRecipient,
+	)]
+	pub rent_recipient: SystemAccount<'info>,
+
+	#[account(
+		mut,
+	)]
+	pub badge_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		token::mint = badge_mint,
+		token::authority = user,
+	)]
+	pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
 	pub system_program: Program<'info, System>,
+	pub token_program: Option<Program<'info, Token2022>>,
 }
 
 /// Revoke a badge from a user
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` badge: [Badge] 
-/// 2. `[writable]` user_badge: [UserBadge] 
-/// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-/// 4. `[signer]` user: [AccountInfo] The user to revoke the badge from
-/// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[writable]` user_badge: [UserBadge] 
+/// 4. `[writable]` revoked_badge: [RevokedBadge] Revocation record for the badge and user
+/// 5. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+/// 6. `[]` user: [AccountInfo] The user to revoke the badge from
+/// 7. `[writable]` rent_recipient: [AccountInfo] The account that funded the user_badge, receives its rent
+/// 8. `[writable, optional]` badge_mint: [Mint] Soulbound NFT mint, required if one was minted for the user_badge
+/// 9. `[writable, optional]` user_token_account: [TokenAccount] The user's token account holding the badge NFT
+/// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 11. `[optional]` token_program: [AccountInfo] Token-2022 program, required if one was minted for the user_badge
+/// 12. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 13. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] The badge ID to revoke
+/// - reason_code: [u8] Why the badge was revoked (see REVOKE_REASON_* constants)
+/// - evidence_uri: [Option<String>] URI to supporting evidence for the revocation
+/// - allow_reissue: [bool] Whether the badge may be issued to this user again
 pub fn handler(
 	ctx: Context<RevokeBadge>,
 	badge_id: u64,
+	reason_code: u8,
+	evidence_uri: Option<String>,
+	allow_reissue: bool,
 ) -> Result<()> {
-    // Validate that the badge exists and is owned by the owner
-    require!(ctx.accounts.badge.owner == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
     
     // Validate that the user has the badge
     require!(ctx.accounts.user_badge.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.badge == ctx.accounts.badge.key(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Validate the revocation details
+    require!(reason_code <= REVOKE_REASON_USER_REQUEST, BadgeRewardsError::InvalidReasonCode);
+    if let Some(evidence_uri) = &evidence_uri {
+        require!(evidence_uri.len() <= 200, BadgeRewardsError::EvidenceUriTooLong);
+    }
+    
+    // Record the revocation, replacing any record left by an earlier revocation
+    let revoked_badge = &mut ctx.accounts.revoked_badge;
+    require!(
+        revoked_badge.revoked_at == 0 || revoked_badge.version == REVOKED_BADGE_VERSION,
+        BadgeRewardsError::UnsupportedAccountVersion
+    );
+    revoked_badge.version = REVOKED_BADGE_VERSION;
+    revoked_badge.user_badge = ctx.accounts.user_badge.key();
+    revoked_badge.user = ctx.accounts.user.key();
+    revoked_badge.badge_owner = ctx.accounts.badge.owner;
+    revoked_badge.badge_id = badge_id;
+    revoked_badge.revoked_by = ctx.accounts.badge_owner.key();
+    revoked_badge.revoked_at = Clock::get()?.unix_timestamp;
+    revoked_badge.reason_code = reason_code;
+    revoked_badge.evidence_uri = evidence_uri;
+    revoked_badge.allow_reissue = allow_reissue;
+    revoked_badge.reissued_at = None;
+    revoked_badge.bump = ctx.bumps.revoked_badge;
+    
+    // Burn the soulbound badge NFT using the badge PDA's permanent delegate authority
+    if let Some(nft_mint) = ctx.accounts.user_badge.nft_mint {
+        let badge_mint = ctx.accounts.badge_mint.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        require!(badge_mint.key() == nft_mint, BadgeRewardsError::InvalidBadgeMint);
+        
+        // Nothing to burn if the user has already burned it themselves
+        if badge_mint.supply > 0 {
+            let user_token_account = ctx.accounts.user_token_account.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+            let badge_owner = ctx.accounts.badge.owner;
+            let badge_id_bytes = badge_id.to_le_bytes();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"badge",
+                badge_owner.as_ref(),
+                badge_id_bytes.as_ref(),
+                &[ctx.accounts.badge.bump],
+            ]];
+            let cpi_accounts = Burn {
+                mint: badge_mint.to_account_info(),
+                from: user_token_account.to_account_info(),
+                authority: ctx.accounts.badge.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+            burn(cpi_ctx, user_token_account.amount)?;
+        }
+    }
     
     // Revoke the badge from the user
-    // We'll just close the user_badge account to revoke the badge
-    // In a real implementation, you might want to mark it as revoked instead
+    // The user_badge account is closed on exit and its rent returned to the rent_recipient
+    
+    // Accrue yield to the current holders before one leaves; a revoked badge forfeits its unharvested share
+    ctx.accounts.badge.accrue_yield(Clock::get()?.unix_timestamp)?;
+    ctx.accounts.badge.release_yield(&ctx.accounts.user_badge);
     
-    // Update badge total earned count
+    // Update badge held count; total_issued is lifetime and never decremented
     if ctx.accounts.badge.total_earned > 0 {
         ctx.accounts.badge.total_earned = ctx.accounts.badge.total_earned.saturating_sub(1);
     }
     
+    emit_cpi!(BadgeRevoked {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user: ctx.accounts.user.key(),
+        reason_code,
+        allow_reissue,
+        timestamp: ctx.accounts.revoked_badge.revoked_at,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct SetBadgeYield<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	pub owner: Signer<'info>,
+
+	/// The token mint yield is paid in
+	pub yield_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		init_if_needed,
+		payer = fee_payer,
+		seeds = [
+			b"yield_vault",
+			badge.key().as_ref(),
+		],
+		bump,
+		token::mint = yield_mint,
+		token::authority = badge,
+	)]
+	pub yield_vault: InterfaceAccount<'info, TokenAccount>,
+
+	pub system_program: Program<'info, System>,
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Set the rate at which a badge emits yield to its holders
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[signer]` owner: [AccountInfo] The owner of the badge
+/// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+/// 5. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+/// 6. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 9. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to set yield for
+/// - emission_rate: [u64] Yield tokens emitted per second, shared equally among holders, or 0 to stop
+pub fn handler(
+	ctx: Context<SetBadgeYield>,
+	badge_id: u64,
+	emission_rate: u64,
+) -> Result<()> {
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    
+    // Validate that the yield mint matches any set before
+    let yield_mint = ctx.accounts.yield_mint.key();
+    require!(
+        ctx.accounts.badge.yield_mint.is_none() || ctx.accounts.badge.yield_mint == Some(yield_mint),
+        BadgeRewardsError::YieldMintMismatch
+    );
+    
+    // Accrue yield at the old rate before switching to the new one
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.badge.accrue_yield(now)?;
+    ctx.accounts.badge.yield_mint = Some(yield_mint);
+    ctx.accounts.badge.emission_rate = emission_rate;
+    
+    emit_cpi!(BadgeYieldSet {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        yield_mint,
+        emission_rate,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	pub admin: Signer<'info>,
+}
+
+/// Pause or resume all mutating instructions (admin)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[writable]` config: [Config] 
+/// 2. `[signer]` admin: [AccountInfo] The config admin
+/// 3. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 4. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - paused: [bool] Whether the program should be paused
+pub fn handler(
+	ctx: Context<SetPause>,
+	paused: bool,
+) -> Result<()> {
+    // Validate that the signer is the admin
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), BadgeRewardsError::Unauthorized);
+    
+    // Update the pause flag
+    ctx.accounts.config.paused = paused;
+    
+    emit_cpi!(PauseSet {
+        admin: ctx.accounts.config.admin,
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolFee<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	pub admin: Signer<'info>,
+}
+
+/// Change the protocol fee (admin)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[writable]` config: [Config] 
+/// 2. `[signer]` admin: [AccountInfo] The config admin
+/// 3. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 4. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - protocol_fee_bps: [u16] Protocol fee in basis points
+pub fn handler(
+	ctx: Context<SetProtocolFee>,
+	protocol_fee_bps: u16,
+) -> Result<()> {
+    // Validate that the signer is the admin and the fee is in range
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), BadgeRewardsError::Unauthorized);
+    require!(protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS, BadgeRewardsError::InvalidProtocolFee);
+    
+    // Update the fee
+    let previous_fee_bps = ctx.accounts.config.protocol_fee_bps;
+    ctx.accounts.config.protocol_fee_bps = protocol_fee_bps;
+    
+    emit_cpi!(ProtocolFeeSet {
+        admin: ctx.accounts.config.admin,
+        previous_fee_bps,
+        protocol_fee_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -1,12 +1,20 @@
 use crate::*;
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
+use anchor_lang::system_program::{create_account, CreateAccount};
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    associated_token::{create_idempotent, get_associated_token_address_with_program_id, AssociatedToken, Create},
+    token_2022::spl_token_2022::{self, extension::ExtensionType},
+    token_interface::{
+        initialize_mint2, metadata_pointer_initialize, mint_to, non_transferable_mint_initialize,
+        permanent_delegate_initialize, spl_pod::optional_keys::OptionalNonZeroPubkey,
+        spl_token_metadata_interface::state::TokenMetadata, token_metadata_initialize, InitializeMint2,
+        MetadataPointerInitialize, MintTo, NonTransferableMintInitialize, PermanentDelegateInitialize,
+        Token2022, TokenMetadataInitialize,
+    },
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
@@ -17,20 +25,31 @@ pub struct StakeBadge<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
 	#[account(
 		mut,
 		seeds = [
 			b"badge",
-			badge_owner.key().as_ref(),
+			badge.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub badge: Account<'info, Badge>,
 
 	#[account(
 		init,
-		space = 40,
+		space = 8 + UserBadge::INIT_SPACE,
 		payer = fee_payer,
 		seeds = [
 			b"user_badge",
@@ -41,21 +60,76 @@ pub struct StakeBadge<'info> {
 	)]
 	pub user_badge: Account<'info, UserBadge>,
 
+	/// CHECK: Revocation record for the badge and user; only read if one has been created
+	#[account(
+		mut,
+		seeds = [
+			b"revoked_badge",
+			badge.key().as_ref(),
+			user.key().as_ref(),
+		],
+		bump,
+	)]
+	pub revoked_badge: UncheckedAccount<'info>,
+
+	#[account(
+		seeds = [
+			b"user_points",
+			badge.owner.as_ref(),
+			user.key().as_ref(),
+		],
+		bump = user_points.bump,
+		constraint = user_points.version == USER_POINTS_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_points: Account<'info, UserPoints>,
+
 	pub badge_owner: Signer<'info>,
 	pub user: Signer<'info>,
 
+	/// CHECK: Soulbound NFT mint for the badge and user; created by this instruction on first issue
+	#[account(
+		mut,
+		seeds = [
+			b"badge_mint",
+			badge.key().as_ref(),
+			user.key().as_ref(),
+		],
+		bump,
+	)]
+	pub badge_mint: Option<UncheckedAccount<'info>>,
+
+	/// CHECK: The user's associated token account for the badge_mint; created if missing
+	#[account(
+		mut,
+	)]
+	pub user_token_account: Option<UncheckedAccount<'info>>,
+
 	pub system_program: Program<'info, System>,
+	pub token_program: Option<Program<'info, Token2022>>,
+	pub associated_token_program: Option<Program<'info, AssociatedToken>>,
 }
 
 /// Stake a badge (mint it to user)
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` badge: [Badge] 
-/// 2. `[writable]` user_badge: [UserBadge] 
-/// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-/// 4. `[signer]` user: [AccountInfo] The user staking the badge
-/// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[writable]` user_badge: [UserBadge] 
+/// 4. `[writable]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+/// 5. `[]` user_points: [UserPoints] The user's points balance with the badge owner
+/// 6. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+/// 7. `[signer]` user: [AccountInfo] The user staking the badge
+/// 8. `[writable, optional]` badge_mint: [Mint] Soulbound NFT mint, required if the badge mints NFTs
+/// 9. `[writable, optional]` user_token_account: [TokenAccount] The user's associated token account for the badge_mint
+/// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 11. `[optional]` token_program: [AccountInfo] Token-2022 program, required if the badge mints NFTs
+/// 12. `[optional]` associated_token_program: [AccountInfo] Associated token program, required if the badge mints NFTs
+/// 13. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 14. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Remaining accounts:
+/// 0..n. `[]` user_badges: [UserBadge] The user's user_badge for each of the badge's prerequisites, in order
 ///
 /// Data:
 /// - badge_id: [u64] The badge ID to stake
@@ -65,17 +139,176 @@ pub fn handler(
 ) -> Result<()> {
     // Validate that the badge exists and is active
     require!(ctx.accounts.badge.is_active, BadgeRewardsError::BadgeNotActive);
-    require!(ctx.accounts.badge.owner == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.authority == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    
+    // Validate that the badge supply cap has not been reached
+    require!(ctx.accounts.badge.total_issued < ctx.accounts.badge.max_earnings, BadgeRewardsError::MaxEarningsReached);
+    
+    // Validate that the user has earned enough points with this owner
+    require!(ctx.accounts.user_points.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_points.points >= ctx.accounts.badge.required_points, BadgeRewardsError::InsufficientPoints);
+    
+    // Validate that the user holds every prerequisite badge, passed in order as remaining accounts
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.badge.require_prerequisites(&ctx.accounts.user.key(), ctx.remaining_accounts, now)?;
+    
+    // Re-issuing a revoked badge is only allowed if the revocation permits it
+    let revoked_badge_info = ctx.accounts.revoked_badge.to_account_info();
+    if let Some(mut revoked_badge) = RevokedBadge::load(&revoked_badge_info)? {
+        require!(revoked_badge.allow_reissue, BadgeRewardsError::BadgeRevoked);
+        revoked_badge.reissued_at = Some(Clock::get()?.unix_timestamp);
+        revoked_badge.try_serialize(&mut &mut revoked_badge_info.try_borrow_mut_data()?[..])?;
+    }
     
     // Check if user already has this badge
     // Initialize the user badge account
+    ctx.accounts.user_badge.version = USER_BADGE_VERSION;
     ctx.accounts.user_badge.user = ctx.accounts.user.key();
     ctx.accounts.user_badge.badge_id = badge_id;
     ctx.accounts.user_badge.earned_at = Clock::get()?.unix_timestamp;
+    ctx.accounts.user_badge.rent_payer = ctx.accounts.fee_payer.key();
+    ctx.accounts.user_badge.nft_mint = None;
     ctx.accounts.user_badge.bump = ctx.bumps.user_badge;
+    ctx.accounts.user_badge.expires_at = ctx.accounts.badge.expires_at(ctx.accounts.user_badge.earned_at);
+    ctx.accounts.user_badge.tier = 0;
+    ctx.accounts.user_badge.badge = ctx.accounts.badge.key();
+    
+    // Mint a soulbound NFT so the badge shows up in wallets and explorers
+    if ctx.accounts.badge.mint_nft {
+        let badge_mint = ctx.accounts.badge_mint.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        let user_token_account = ctx.accounts.user_token_account.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        let associated_token_program = ctx.accounts.associated_token_program.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        require!(
+            user_token_account.key() == get_associated_token_address_with_program_id(
+                &ctx.accounts.user.key(),
+                &badge_mint.key(),
+                &token_program.key(),
+            ),
+            BadgeRewardsError::InvalidTokenAccount
+        );
+        
+        // The badge PDA is the mint authority, metadata update authority and permanent delegate
+        let badge_key = ctx.accounts.badge.key();
+        let badge_owner = ctx.accounts.badge.owner;
+        let badge_id_bytes = badge_id.to_le_bytes();
+        let badge_signer_seeds: &[&[&[u8]]] = &[&[
+            b"badge",
+            badge_owner.as_ref(),
+            badge_id_bytes.as_ref(),
+            &[ctx.accounts.badge.bump],
+        ]];
+        
+        // A re-issued badge reuses the mint left behind by the earlier revocation
+        if badge_mint.data_is_empty() {
+            let user_key = ctx.accounts.user.key();
+            let mint_signer_seeds: &[&[&[u8]]] = &[&[
+                b"badge_mint",
+                badge_key.as_ref(),
+                user_key.as_ref(),
+                &[ctx.bumps.badge_mint.ok_or(BadgeRewardsError::MissingBadgeMint)?],
+            ]];
+            
+            // Fund the mint for its extensions plus the token metadata written after initialization
+            let metadata = TokenMetadata {
+                update_authority: OptionalNonZeroPubkey::try_from(Some(badge_key))?,
+                mint: badge_mint.key(),
+                name: ctx.accounts.badge.name.clone(),
+                symbol: BADGE_NFT_SYMBOL.to_string(),
+                uri: ctx.accounts.badge.icon_uri.clone(),
+                additional_metadata: vec![],
+            };
+            let mint_space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+                ExtensionType::MetadataPointer,
+                ExtensionType::NonTransferable,
+                ExtensionType::PermanentDelegate,
+            ])?;
+            let lamports = Rent::get()?.minimum_balance(mint_space + metadata.tlv_size_of()?);
+            
+            let cpi_accounts = CreateAccount {
+                from: ctx.accounts.fee_payer.to_account_info(),
+                to: badge_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), cpi_accounts, mint_signer_seeds);
+            create_account(cpi_ctx, lamports, mint_space as u64, &token_program.key())?;
+            
+            // Extensions must be initialized before the mint itself
+            let cpi_accounts = MetadataPointerInitialize {
+                token_program_id: token_program.to_account_info(),
+                mint: badge_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            metadata_pointer_initialize(cpi_ctx, Some(badge_key), Some(badge_mint.key()))?;
+            
+            let cpi_accounts = NonTransferableMintInitialize {
+                token_program_id: token_program.to_account_info(),
+                mint: badge_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            non_transferable_mint_initialize(cpi_ctx)?;
+            
+            let cpi_accounts = PermanentDelegateInitialize {
+                token_program_id: token_program.to_account_info(),
+                mint: badge_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            permanent_delegate_initialize(cpi_ctx, &badge_key)?;
+            
+            let cpi_accounts = InitializeMint2 {
+                mint: badge_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            initialize_mint2(cpi_ctx, 0, &badge_key, None)?;
+            
+            let cpi_accounts = TokenMetadataInitialize {
+                program_id: token_program.to_account_info(),
+                metadata: badge_mint.to_account_info(),
+                update_authority: ctx.accounts.badge.to_account_info(),
+                mint_authority: ctx.accounts.badge.to_account_info(),
+                mint: badge_mint.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, badge_signer_seeds);
+            token_metadata_initialize(cpi_ctx, metadata.name, metadata.symbol, metadata.uri)?;
+        }
+        
+        // Create the user's token account and mint the badge NFT into it
+        let cpi_accounts = Create {
+            payer: ctx.accounts.fee_payer.to_account_info(),
+            associated_token: user_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+            mint: badge_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(associated_token_program.to_account_info(), cpi_accounts);
+        create_idempotent(cpi_ctx)?;
+        
+        let cpi_accounts = MintTo {
+            mint: badge_mint.to_account_info(),
+            to: user_token_account.to_account_info(),
+            authority: ctx.accounts.badge.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, badge_signer_seeds);
+        mint_to(cpi_ctx, 1)?;
+        
+        ctx.accounts.user_badge.nft_mint = Some(badge_mint.key());
+    }
     
-    // Update badge total earned count
+    // Accrue yield to the current holders, then start this badge's yield from now
+    ctx.accounts.badge.accrue_yield(ctx.accounts.user_badge.earned_at)?;
+    ctx.accounts.user_badge.yield_debt = ctx.accounts.badge.acc_yield_per_badge;
+    
+    // Update badge issued and held counts
+    ctx.accounts.badge.total_issued = ctx.accounts.badge.total_issued.saturating_add(1);
     ctx.accounts.badge.total_earned = ctx.accounts.badge.total_earned.saturating_add(1);
     
+    emit_cpi!(BadgeStaked {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user: ctx.accounts.user.key(),
+        total_issued: ctx.accounts.badge.total_issued,
+        timestamp: ctx.accounts.user_badge.earned_at,
+    });
+    
     Ok(())
 }
\ No newline at end of file
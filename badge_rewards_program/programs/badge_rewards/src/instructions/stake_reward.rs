@@ -0,0 +1,92 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct StakeReward<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	pub business_owner: Signer<'info>,
+}
+
+/// Stake a reward to hold it back from claiming (business user)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` reward: [Reward] 
+/// 3. `[signer]` business_owner: [AccountInfo] The owner of the reward
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward is associated with
+/// - reward_id: [u64] The reward ID to stake
+/// - min_stake_period: [u64] Minimum seconds to keep the reward staked (0 = no minimum)
+pub fn handler(
+	ctx: Context<StakeReward>,
+	badge_id: u64,
+	reward_id: u64,
+	min_stake_period: u64,
+) -> Result<()> {
+    // Validate that the reward exists and the signer is its authority
+    require!(ctx.accounts.reward.authority == ctx.accounts.business_owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    
+    // Validate that the reward is not already live or staked
+    require!(!ctx.accounts.reward.is_active, BadgeRewardsError::RewardAlreadyActive);
+    require!(ctx.accounts.reward.stake_status == StakeStatus::Available, BadgeRewardsError::RewardAlreadyStaked);
+    require!(min_stake_period <= i64::MAX as u64, BadgeRewardsError::InvalidStakePeriod);
+    
+    // Stake the reward
+    let now = Clock::get()?.unix_timestamp;
+    let reward = &mut ctx.accounts.reward;
+    reward.stake_status = StakeStatus::StakedByBusiness;
+    // Recorded against the stable owner so a handover does not strand the stake
+    reward.staked_by = reward.owner;
+    reward.staked_at = now;
+    reward.min_stake_period = min_stake_period;
+    reward.can_unstake_at = now.saturating_add(min_stake_period as i64);
+    
+    emit_cpi!(RewardStaked {
+        owner: ctx.accounts.reward.owner,
+        badge_id,
+        reward_id,
+        staked_by: ctx.accounts.business_owner.key(),
+        min_stake_period,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	owner: Pubkey,
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct StakeRewardPlayer<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"reward",
+			owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_reward",
+			player.key().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = user_reward.bump,
+		constraint = user_reward.version == USER_REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_reward: Account<'info, UserReward>,
+
+	pub player: Signer<'info>,
+
+	/// The token mint for the reward
+	pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = player,
+	)]
+	pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	#[account(
+		init,
+		payer = fee_payer,
+		seeds = [
+			b"user_reward_escrow",
+			user_reward.key().as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+		token::authority = user_reward,
+	)]
+	pub reward_escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	pub system_program: Program<'info, System>,
+	pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Lock a claimed reward for a minimum period (player)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` reward: [Reward] 
+/// 3. `[writable]` user_reward: [UserReward] 
+/// 4. `[signer]` player: [AccountInfo] The player who claimed the reward
+/// 5. `[optional]` reward_mint: [Mint] The token mint, required for token and NFT rewards
+/// 6. `[writable, optional]` player_token_account: [TokenAccount] The player's token account holding the reward
+/// 7. `[writable, optional]` reward_escrow: [TokenAccount] Escrow the reward is locked in
+/// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 9. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 10. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 11. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - owner: [Pubkey] The owner of the reward
+/// - badge_id: [u64] The badge ID the reward is associated with
+/// - reward_id: [u64] The reward ID to lock
+/// - min_stake_period: [u64] Minimum seconds to keep the reward locked (0 = no minimum)
+pub fn handler(
+	ctx: Context<StakeRewardPlayer>,
+	_owner: Pubkey,
+	_badge_id: u64,
+	reward_id: u64,
+	min_stake_period: u64,
+) -> Result<()> {
+    // Validate that the player claimed this reward and it isn't already locked
+    require!(ctx.accounts.user_reward.user == ctx.accounts.player.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    require!(ctx.accounts.user_reward.reward == ctx.accounts.reward.key(), BadgeRewardsError::InvalidUserReward);
+    require!(ctx.accounts.user_reward.stake_status == StakeStatus::Available, BadgeRewardsError::RewardAlreadyStaked);
+    require!(min_stake_period <= i64::MAX as u64, BadgeRewardsError::InvalidStakePeriod);
+    
+    // Token and NFT rewards are moved into escrow for the lock period
+    let mut locked_amount = ctx.accounts.user_reward.claimed_amount;
+    if locked_amount > 0 {
+        let mint = match ctx.accounts.reward.reward_type {
+            0 => ctx.accounts.reward.token_mint.ok_or(BadgeRewardsError::MissingTokenMint)?,
+            1 => ctx.accounts.reward.nft_mint.ok_or(BadgeRewardsError::MissingNftMint)?,
+            _ => return Err(BadgeRewardsError::InvalidRewardType.into()),
+        };
+        let reward_mint = ctx.accounts.reward_mint.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        let player_token_account = ctx.accounts.player_token_account.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        let reward_escrow = ctx.accounts.reward_escrow.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        require!(reward_mint.key() == mint, BadgeRewardsError::InvalidTokenMint);
+        
+        // Only lock what the player still holds; a vesting reward sits in its vesting vault until withdrawn
+        let amount = locked_amount.min(player_token_account.amount);
+        require!(amount > 0, BadgeRewardsError::NothingToLock);
+        
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            from: player_token_account.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: reward_escrow.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, reward_mint.decimals)?;
+        
+        // Lock what the escrow actually received, net of any transfer fee
+        let mut reward_escrow = reward_escrow.clone();
+        reward_escrow.reload()?;
+        locked_amount = reward_escrow.amount;
+    }
+    
+    // Lock the reward
+    let now = Clock::get()?.unix_timestamp;
+    let user_reward = &mut ctx.accounts.user_reward;
+    user_reward.stake_status = StakeStatus::StakedByPlayer;
+    user_reward.staked_at = now;
+    user_reward.min_stake_period = min_stake_period;
+    user_reward.can_unstake_at = now.saturating_add(min_stake_period as i64);
+    user_reward.locked_amount = locked_amount;
+    
+    emit_cpi!(PlayerRewardStaked {
+        owner: ctx.accounts.reward.owner,
+        reward_id,
+        player: ctx.accounts.player.key(),
+        min_stake_period,
+        locked_amount,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
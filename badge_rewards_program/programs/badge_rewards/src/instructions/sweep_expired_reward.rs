@@ -0,0 +1,131 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct SweepExpiredReward<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	/// The token mint held by the reward vault
+	pub reward_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = reward.authority,
+	)]
+	pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward_vault",
+			reward.key().as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+	)]
+	pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Return a reward's unclaimed vault balance to its authority once the claim window has closed (permissionless)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` reward: [Reward] 
+/// 3. `[]` reward_mint: [Mint] The token mint held by the reward vault
+/// 4. `[writable]` authority_token_account: [TokenAccount] The reward authority's token account to return the balance to
+/// 5. `[writable]` reward_vault: [TokenAccount] Program-owned vault claims are paid from
+/// 6. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 7. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 8. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward is associated with
+/// - reward_id: [u64] The reward ID
+pub fn handler(
+	ctx: Context<SweepExpiredReward>,
+	badge_id: u64,
+	reward_id: u64,
+) -> Result<()> {
+    // Validate that the reward exists and its claim window has closed
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    let now = Clock::get()?.unix_timestamp;
+    let claim_end = ctx.accounts.reward.claim_end.ok_or(BadgeRewardsError::ClaimWindowNotClosed)?;
+    require!(now >= claim_end, BadgeRewardsError::ClaimWindowNotClosed);
+    
+    // Validate that there is something left to sweep
+    let amount = ctx.accounts.reward_vault.amount;
+    require!(amount > 0, BadgeRewardsError::InsufficientVaultBalance);
+    
+    // Transfer the whole vault balance to the authority, signed by the reward PDA
+    let owner_key = ctx.accounts.reward.owner;
+    let badge_id_bytes = badge_id.to_le_bytes();
+    let reward_id_bytes = reward_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"reward",
+        owner_key.as_ref(),
+        badge_id_bytes.as_ref(),
+        reward_id_bytes.as_ref(),
+        &[ctx.accounts.reward.bump],
+    ]];
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.authority_token_account.to_account_info(),
+        authority: ctx.accounts.reward.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+    
+    // Nothing is left to claim
+    ctx.accounts.reward.is_active = false;
+    
+    emit_cpi!(RewardSwept {
+        owner: owner_key,
+        badge_id,
+        reward_id,
+        recipient: ctx.accounts.reward.authority,
+        amount,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
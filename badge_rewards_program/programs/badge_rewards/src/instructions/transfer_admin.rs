@@ -0,0 +1,55 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	pub admin: Signer<'info>,
+}
+
+/// Hand the config admin role to a new key (admin)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[writable]` config: [Config] 
+/// 2. `[signer]` admin: [AccountInfo] The current config admin
+/// 3. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 4. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - new_admin: [Pubkey] The key that becomes admin
+pub fn handler(
+	ctx: Context<TransferAdmin>,
+	new_admin: Pubkey,
+) -> Result<()> {
+    // Validate that the signer is the admin
+    require!(ctx.accounts.config.admin == ctx.accounts.admin.key(), BadgeRewardsError::Unauthorized);
+    require!(new_admin != Pubkey::default(), BadgeRewardsError::InvalidAdmin);
+    
+    // Hand over the admin role
+    let previous_admin = ctx.accounts.config.admin;
+    ctx.accounts.config.admin = new_admin;
+    
+    emit_cpi!(AdminTransferred {
+        previous_admin,
+        new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
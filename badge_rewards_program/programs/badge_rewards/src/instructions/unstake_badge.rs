@@ -1,6 +1,9 @@
 use crate::*;
 use anchor_lang::prelude::*;
 
+use anchor_spl::token_interface::{burn, Burn, Mint, Token2022, TokenAccount, TokenInterface};
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
@@ -11,43 +14,112 @@ pub struct UnstakeBadge<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
 	#[account(
 		mut,
 		seeds = [
 			b"badge",
-			badge_owner.key().as_ref(),
+			badge.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub badge: Account<'info, Badge>,
 
 	#[account(
 		mut,
+		close = rent_recipient,
 		seeds = [
 			b"user_badge",
 			user.key().as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = user_badge.bump,
+		constraint = user_badge.version == USER_BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub user_badge: Account<'info, UserBadge>,
 
 	pub badge_owner: Signer<'info>,
 	pub user: Signer<'info>,
 
+	#[account(
+		mut,
+		address = user_badge.rent_payer @ BadgeRewardsError::InvalidRentRecipient,
+	)]
+	pub rent_recipient: SystemAccount<'info>,
+
+	#[account(
+		mut,
+	)]
+	pub badge_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		token::mint = badge_mint,
+		token::authority = user,
+	)]
+	pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
 	pub system_program: Program<'info, System>,
+	pub token_program: Option<Program<'info, Token2022>>,
+
+	/// The token mint yield is paid in
+	#[account(
+		constraint = badge.yield_mint == Some(yield_mint.key()) @ BadgeRewardsError::YieldMintMismatch,
+	)]
+	pub yield_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"yield_vault",
+			badge.key().as_ref(),
+		],
+		bump,
+		token::mint = yield_mint,
+	)]
+	pub yield_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+	#[account(
+		mut,
+		token::mint = yield_mint,
+		token::authority = user,
+	)]
+	pub user_yield_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+	pub yield_token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 /// Unstake a badge
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` badge: [Badge] 
-/// 2. `[writable]` user_badge: [UserBadge] 
-/// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-/// 4. `[signer]` user: [AccountInfo] The user unstaking the badge
-/// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[writable]` user_badge: [UserBadge] 
+/// 4. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+/// 5. `[signer]` user: [AccountInfo] The user unstaking the badge
+/// 6. `[writable]` rent_recipient: [AccountInfo] The account that funded the user_badge, receives its rent
+/// 7. `[writable, optional]` badge_mint: [Mint] Soulbound NFT mint, required if one was minted for the user_badge
+/// 8. `[writable, optional]` user_token_account: [TokenAccount] The user's token account holding the badge NFT
+/// 9. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+/// 10. `[optional]` token_program: [AccountInfo] Token-2022 program, required if one was minted for the user_badge
+/// 11. `[optional]` yield_mint: [Mint] The token mint yield is paid in; without the yield accounts unharvested yield is forfeited
+/// 12. `[writable, optional]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+/// 13. `[writable, optional]` user_yield_account: [TokenAccount] The user's token account to pay unharvested yield to, up to the vault balance
+/// 14. `[optional]` yield_token_program: [AccountInfo] Token program of the yield mint
+/// 15. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 16. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] The badge ID to unstake
@@ -55,22 +127,77 @@ pub fn handler(
 	ctx: Context<UnstakeBadge>,
 	badge_id: u64,
 ) -> Result<()> {
-    // Validate that the badge exists and is owned by the owner
-    require!(ctx.accounts.badge.owner == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.badge_owner.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
     
     // Validate that the user has the badge
     require!(ctx.accounts.user_badge.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.badge == ctx.accounts.badge.key(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Burn the soulbound badge NFT using the badge PDA's permanent delegate authority
+    if let Some(nft_mint) = ctx.accounts.user_badge.nft_mint {
+        let badge_mint = ctx.accounts.badge_mint.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+        require!(badge_mint.key() == nft_mint, BadgeRewardsError::InvalidBadgeMint);
+        
+        // Nothing to burn if the user has already burned it themselves
+        if badge_mint.supply > 0 {
+            let user_token_account = ctx.accounts.user_token_account.as_ref().ok_or(BadgeRewardsError::MissingBadgeMint)?;
+            let badge_owner = ctx.accounts.badge.owner;
+            let badge_id_bytes = badge_id.to_le_bytes();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"badge",
+                badge_owner.as_ref(),
+                badge_id_bytes.as_ref(),
+                &[ctx.accounts.badge.bump],
+            ]];
+            let cpi_accounts = Burn {
+                mint: badge_mint.to_account_info(),
+                from: user_token_account.to_account_info(),
+                authority: ctx.accounts.badge.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+            burn(cpi_ctx, user_token_account.amount)?;
+        }
+    }
     
     // Unstake the badge from the user
-    // We'll just close the user_badge account to remove the badge
-    // In a real implementation, you might want to mark it as un-staked instead
+    // The user_badge account is closed on exit and its rent returned to the rent_recipient
+    
+    // Accrue yield to the current holders before one leaves, then pay out this badge's unharvested share
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.badge.accrue_yield(now)?;
+    let yield_paid = settle_yield(
+        &mut ctx.accounts.badge,
+        &ctx.accounts.user_badge,
+        ctx.accounts.yield_mint.as_ref(),
+        ctx.accounts.yield_vault.as_deref(),
+        ctx.accounts.user_yield_account.as_deref(),
+        ctx.accounts.yield_token_program.as_ref(),
+    )?;
+    if yield_paid > 0 {
+        emit_cpi!(YieldHarvested {
+            owner: ctx.accounts.badge.owner,
+            badge_id,
+            user: ctx.accounts.user.key(),
+            amount: yield_paid,
+            timestamp: now,
+        });
+    }
     
-    // Update badge total earned count
+    // Update badge held count; total_issued is lifetime and never decremented
     if ctx.accounts.badge.total_earned > 0 {
         ctx.accounts.badge.total_earned = ctx.accounts.badge.total_earned.saturating_sub(1);
     }
     
+    emit_cpi!(BadgeUnstaked {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+	reward_id: u64,
+)]
+pub struct UnstakeReward<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"reward",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub reward: Account<'info, Reward>,
+
+	pub business_owner: Signer<'info>,
+}
+
+/// Unstake a reward to make it available for claiming (business user)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` reward: [Reward] 
+/// 3. `[signer]` business_owner: [AccountInfo] The owner of the reward
+/// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 5. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID this reward is associated with
+/// - reward_id: [u64] The reward ID to unstake
+pub fn handler(
+	ctx: Context<UnstakeReward>,
+	badge_id: u64,
+	reward_id: u64,
+) -> Result<()> {
+    // Validate that the reward exists and the signer is its authority
+    require!(ctx.accounts.reward.authority == ctx.accounts.business_owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
+    
+    // Validate that the reward is staked by the business and the minimum period has elapsed;
+    // whoever holds the authority now may unstake, even if the stake predates a handover
+    require!(ctx.accounts.reward.stake_status == StakeStatus::StakedByBusiness, BadgeRewardsError::RewardNotStaked);
+    require!(Clock::get()?.unix_timestamp >= ctx.accounts.reward.can_unstake_at, BadgeRewardsError::MinStakePeriodNotMet);
+    
+    // Make the reward available for claiming
+    let reward = &mut ctx.accounts.reward;
+    reward.stake_status = StakeStatus::Available;
+    reward.is_active = true;
+    reward.staked_by = Pubkey::default();
+    reward.staked_at = 0;
+    reward.can_unstake_at = 0;
+    
+    emit_cpi!(RewardUnstaked {
+        owner: ctx.accounts.reward.owner,
+        badge_id,
+        reward_id,
+        unstaked_by: ctx.accounts.business_owner.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
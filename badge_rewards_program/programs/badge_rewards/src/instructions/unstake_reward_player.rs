@@ -0,0 +1,151 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	reward_id: u64,
+)]
+pub struct UnstakeRewardPlayer<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_reward",
+			player.key().as_ref(),
+			reward_id.to_le_bytes().as_ref(),
+		],
+		bump = user_reward.bump,
+		constraint = user_reward.version == USER_REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_reward: Account<'info, UserReward>,
+
+	#[account(
+		mut,
+	)]
+	pub player: Signer<'info>,
+
+	/// The token mint held in escrow
+	#[account(
+		mut,
+	)]
+	pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = player,
+	)]
+	pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_reward_escrow",
+			user_reward.key().as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+	)]
+	pub reward_escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
+	pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Unlock a claimed reward after its minimum period (player)
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` user_reward: [UserReward] 
+/// 3. `[writable, signer]` player: [AccountInfo] The player who locked the reward, receives the escrow rent
+/// 4. `[writable, optional]` reward_mint: [Mint] The token mint held in escrow, receives the escrow's withheld transfer fees
+/// 5. `[writable, optional]` player_token_account: [TokenAccount] The player's token account to release to
+/// 6. `[writable, optional]` reward_escrow: [TokenAccount] Escrow the reward is locked in
+/// 7. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 9. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - reward_id: [u64] The reward ID to unlock
+pub fn handler(
+	ctx: Context<UnstakeRewardPlayer>,
+	reward_id: u64,
+) -> Result<()> {
+    // Validate that the player locked this reward and the minimum period has elapsed
+    require!(ctx.accounts.user_reward.user == ctx.accounts.player.key(), BadgeRewardsError::UnauthorizedUnstake);
+    require!(ctx.accounts.user_reward.stake_status == StakeStatus::StakedByPlayer, BadgeRewardsError::RewardNotStaked);
+    require!(Clock::get()?.unix_timestamp >= ctx.accounts.user_reward.can_unstake_at, BadgeRewardsError::MinStakePeriodNotMet);
+    
+    // Release escrowed tokens back to the player, signed by the user_reward PDA
+    let unlocked_amount = ctx.accounts.user_reward.locked_amount;
+    let player_key = ctx.accounts.player.key();
+    let reward_id_bytes = reward_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"user_reward",
+        player_key.as_ref(),
+        reward_id_bytes.as_ref(),
+        &[ctx.accounts.user_reward.bump],
+    ]];
+    if unlocked_amount > 0 {
+        let reward_mint = ctx.accounts.reward_mint.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        let player_token_account = ctx.accounts.player_token_account.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        let reward_escrow = ctx.accounts.reward_escrow.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        
+        let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+            from: reward_escrow.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: player_token_account.to_account_info(),
+            authority: ctx.accounts.user_reward.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+        anchor_spl::token_interface::transfer_checked(cpi_ctx, unlocked_amount, reward_mint.decimals)?;
+    }
+    
+    // Close the escrow whenever one was passed, returning its rent to the player
+    if let Some(reward_escrow) = ctx.accounts.reward_escrow.as_ref() {
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(BadgeRewardsError::MissingRewardEscrow)?;
+        close_token_account(
+            reward_escrow.to_account_info(),
+            ctx.accounts.reward_mint.as_ref().map(|mint| mint.to_account_info()),
+            ctx.accounts.player.to_account_info(),
+            ctx.accounts.user_reward.to_account_info(),
+            token_program.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+    
+    // Unlock the reward
+    let user_reward = &mut ctx.accounts.user_reward;
+    user_reward.stake_status = StakeStatus::Available;
+    user_reward.staked_at = 0;
+    user_reward.can_unstake_at = 0;
+    user_reward.locked_amount = 0;
+    
+    emit_cpi!(PlayerRewardUnstaked {
+        owner: ctx.accounts.user_reward.owner,
+        reward_id,
+        player: ctx.accounts.player.key(),
+        unlocked_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
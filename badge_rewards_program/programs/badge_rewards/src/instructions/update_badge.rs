@@ -1,9 +1,18 @@
 use crate::*;
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
+	name: Option<String>,
+	description: Option<String>,
+	icon_uri: Option<String>,
+	required_points: Option<u64>,
+	max_earnings: Option<u64>,
+	is_active: Option<bool>,
+	tiers: Option<Vec<BadgeTier>>,
+	prerequisites: Option<Vec<BadgePrerequisite>>,
 )]
 pub struct UpdateBadge<'info> {
 	#[account(
@@ -11,26 +20,52 @@ pub struct UpdateBadge<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
 	#[account(
 		mut,
+		realloc = Badge::space(
+			name.as_deref().unwrap_or(&badge.name),
+			description.as_deref().unwrap_or(&badge.description),
+			icon_uri.as_deref().unwrap_or(&badge.icon_uri),
+			tiers.as_deref().unwrap_or(&badge.tiers),
+			prerequisites.as_deref().unwrap_or(&badge.prerequisites),
+		),
+		realloc::payer = fee_payer,
+		realloc::zero = false,
 		seeds = [
 			b"badge",
-			owner.key().as_ref(),
+			badge.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 		],
 		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub badge: Account<'info, Badge>,
 
 	pub owner: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
 }
 
 /// Update badge information
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` badge: [Badge] 
-/// 2. `[signer]` owner: [AccountInfo] The owner of the badge
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[signer]` owner: [AccountInfo] The owner of the badge
+/// 4. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+/// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 6. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] Unique identifier for this badge
@@ -40,6 +75,8 @@ pub struct UpdateBadge<'info> {
 /// - required_points: [Option<u64>] Points required to earn this badge
 /// - max_earnings: [Option<u64>] Maximum number of times this badge can be earned
 /// - is_active: [Option<bool>] Whether this badge is currently available for earning
+/// - tiers: [Option<Vec<BadgeTier>>] Upgrade levels above the base badge, ordered by increasing required_points
+/// - prerequisites: [Option<Vec<BadgePrerequisite>>] Badges the user must already hold to earn this one
 pub fn handler(
 	ctx: Context<UpdateBadge>,
 	badge_id: u64,
@@ -49,9 +86,11 @@ pub fn handler(
 	required_points: Option<u64>,
 	max_earnings: Option<u64>,
 	is_active: Option<bool>,
+	tiers: Option<Vec<BadgeTier>>,
+	prerequisites: Option<Vec<BadgePrerequisite>>,
 ) -> Result<()> {
-    // Validate that the badge exists and is owned by the owner
-    require!(ctx.accounts.badge.owner == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
     
     // Update badge fields if provided
@@ -77,12 +116,39 @@ pub fn handler(
     
     if let Some(max_earnings) = max_earnings {
         require!(max_earnings > 0, BadgeRewardsError::InvalidMaxEarnings);
+        require!(max_earnings >= ctx.accounts.badge.total_issued, BadgeRewardsError::InvalidMaxEarnings);
         ctx.accounts.badge.max_earnings = max_earnings;
     }
     
     if let Some(is_active) = is_active {
+        if !is_active && ctx.accounts.badge.is_active {
+            ctx.accounts.badge.deactivated_at = Clock::get()?.unix_timestamp;
+        } else if is_active {
+            ctx.accounts.badge.deactivated_at = 0;
+        }
         ctx.accounts.badge.is_active = is_active;
     }
     
+    if let Some(tiers) = tiers {
+        ctx.accounts.badge.tiers = tiers;
+    }
+    
+    // Tiers must stay above the base required_points, whichever of the two changed
+    ctx.accounts.badge.validate_tiers()?;
+    
+    if let Some(prerequisites) = prerequisites {
+        ctx.accounts.badge.prerequisites = prerequisites;
+        ctx.accounts.badge.validate_prerequisites()?;
+    }
+    
+    emit_cpi!(BadgeUpdated {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        required_points: ctx.accounts.badge.required_points,
+        max_earnings: ctx.accounts.badge.max_earnings,
+        is_active: ctx.accounts.badge.is_active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
@@ -1,10 +1,13 @@
 use crate::*;
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(
 	badge_id: u64,
 	reward_id: u64,
+	name: Option<String>,
+	description: Option<String>,
 )]
 pub struct UpdateReward<'info> {
 	#[account(
@@ -12,27 +15,73 @@ pub struct UpdateReward<'info> {
 	)]
 	pub fee_payer: Signer<'info>,
 
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			reward.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
 	#[account(
 		mut,
+		realloc = Reward::space(
+			name.as_deref().unwrap_or(&reward.name),
+			description.as_deref().unwrap_or(&reward.description),
+		),
+		realloc::payer = fee_payer,
+		realloc::zero = false,
 		seeds = [
 			b"reward",
-			owner.key().as_ref(),
+			reward.owner.as_ref(),
 			badge_id.to_le_bytes().as_ref(),
 			reward_id.to_le_bytes().as_ref(),
 		],
 		bump = reward.bump,
+		constraint = reward.version == REWARD_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
 	)]
 	pub reward: Account<'info, Reward>,
 
+	/// CHECK: The reward vault, only checked for whether it has been created
+	#[account(
+		seeds = [
+			b"reward_vault",
+			reward.key().as_ref(),
+		],
+		bump,
+	)]
+	pub reward_vault: UncheckedAccount<'info>,
+
 	pub owner: Signer<'info>,
+
+	pub system_program: Program<'info, System>,
 }
 
 /// Update reward information
 ///
 /// Accounts:
 /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-/// 1. `[writable]` reward: [Reward] 
-/// 2. `[signer]` owner: [AccountInfo] The owner of the reward
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` badge: [Badge] The badge the reward is for
+/// 3. `[writable]` reward: [Reward] 
+/// 4. `[]` reward_vault: [TokenAccount] The reward vault, may be uninitialized
+/// 5. `[signer]` owner: [AccountInfo] The owner of the reward
+/// 6. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+/// 7. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 8. `[]` program: [AccountInfo] Auto-generated, this program
 ///
 /// Data:
 /// - badge_id: [u64] The badge ID this reward is associated with
@@ -44,6 +93,11 @@ pub struct UpdateReward<'info> {
 /// - token_mint: [Option<Pubkey>] Token mint for token rewards
 /// - nft_mint: [Option<Pubkey>] NFT mint for NFT rewards
 /// - is_active: [Option<bool>] Whether this reward is currently available
+/// - min_tier: [Option<u8>] Lowest badge tier that may claim this reward, 0 being the base badge
+/// - max_claims: [Option<Option<u64>>] New cap on how many users may claim this reward, or Some(None) to remove the cap
+/// - claim_start: [Option<Option<i64>>] New time claims open, or Some(None) to open them immediately
+/// - claim_end: [Option<Option<i64>>] New time claims close, or Some(None) to never close them
+/// - vesting: [Option<Option<VestingSchedule>>] New vesting schedule for future claims, or Some(None) to pay them out immediately
 pub fn handler(
 	ctx: Context<UpdateReward>,
 	badge_id: u64,
@@ -55,12 +109,21 @@ pub fn handler(
 	token_mint: Option<Pubkey>,
 	nft_mint: Option<Pubkey>,
 	is_active: Option<bool>,
+	min_tier: Option<u8>,
+	max_claims: Option<Option<u64>>,
+	claim_start: Option<Option<i64>>,
+	claim_end: Option<Option<i64>>,
+	vesting: Option<Option<VestingSchedule>>,
 ) -> Result<()> {
-    // Validate that the reward exists and is owned by the owner
-    require!(ctx.accounts.reward.owner == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    // Validate that the reward exists and the signer is its authority
+    require!(ctx.accounts.reward.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
     require!(ctx.accounts.reward.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
     require!(ctx.accounts.reward.reward_id == reward_id, BadgeRewardsError::InvalidRewardId);
     
+    // The type and mints are fixed once the vault holds the reward or someone has claimed it
+    let terms = (ctx.accounts.reward.reward_type, ctx.accounts.reward.token_mint, ctx.accounts.reward.nft_mint);
+    let terms_locked = !ctx.accounts.reward_vault.data_is_empty() || ctx.accounts.reward.total_claimed > 0;
+    
     // Update reward fields if provided
     if let Some(name) = name {
         require!(name.len() <= 100, BadgeRewardsError::NameTooLong);
@@ -102,9 +165,56 @@ pub fn handler(
         }
     }
     
+    if terms_locked {
+        require!(
+            (ctx.accounts.reward.reward_type, ctx.accounts.reward.token_mint, ctx.accounts.reward.nft_mint) == terms,
+            BadgeRewardsError::RewardTermsLocked
+        );
+    }
+    
     if let Some(is_active) = is_active {
         ctx.accounts.reward.is_active = is_active;
     }
     
+    if let Some(min_tier) = min_tier {
+        require!(min_tier as usize <= ctx.accounts.badge.tiers.len(), BadgeRewardsError::InvalidMinTier);
+        ctx.accounts.reward.min_tier = min_tier;
+    }
+    
+    if let Some(max_claims) = max_claims {
+        if let Some(max_claims) = max_claims {
+            require!(max_claims > 0 && max_claims >= ctx.accounts.reward.total_claimed, BadgeRewardsError::InvalidMaxClaims);
+        }
+        ctx.accounts.reward.max_claims = max_claims;
+    }
+    
+    if let Some(claim_start) = claim_start {
+        ctx.accounts.reward.claim_start = claim_start;
+    }
+    
+    if let Some(claim_end) = claim_end {
+        ctx.accounts.reward.claim_end = claim_end;
+    }
+    
+    // The window must stay ordered, whichever end changed
+    ctx.accounts.reward.validate_claim_window()?;
+    
+    if let Some(vesting) = vesting {
+        ctx.accounts.reward.vesting = vesting;
+    }
+    
+    // A schedule stays valid only on token rewards, whichever of the two changed
+    ctx.accounts.reward.validate_vesting()?;
+    
+    emit_cpi!(RewardUpdated {
+        owner: ctx.accounts.reward.owner,
+        badge_id,
+        reward_id,
+        reward_type: ctx.accounts.reward.reward_type,
+        reward_value: ctx.accounts.reward.reward_value,
+        is_active: ctx.accounts.reward.is_active,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     Ok(())
 }
\ No newline at end of file
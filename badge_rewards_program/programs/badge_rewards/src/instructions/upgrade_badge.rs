@@ -0,0 +1,107 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct UpgradeBadge<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"user_badge",
+			user.key().as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = user_badge.bump,
+		constraint = user_badge.version == USER_BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_badge: Account<'info, UserBadge>,
+
+	#[account(
+		seeds = [
+			b"user_points",
+			badge.owner.as_ref(),
+			user.key().as_ref(),
+		],
+		bump = user_points.bump,
+		constraint = user_points.version == USER_POINTS_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub user_points: Account<'info, UserPoints>,
+
+	pub user: Signer<'info>,
+}
+
+/// Promote a held badge to the highest tier the user's points qualify for
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[]` badge: [Badge] 
+/// 3. `[writable]` user_badge: [UserBadge] 
+/// 4. `[]` user_points: [UserPoints] The user's points balance with the badge owner
+/// 5. `[signer]` user: [AccountInfo] The user holding the badge
+/// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 7. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to upgrade
+pub fn handler(
+	ctx: Context<UpgradeBadge>,
+	badge_id: u64,
+) -> Result<()> {
+    // Validate that the user has the badge
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.user_badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    require!(ctx.accounts.user_badge.badge == ctx.accounts.badge.key(), BadgeRewardsError::UserBadgeMismatch);
+    
+    // Validate that the user's badge is still valid
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(expires_at) = ctx.accounts.user_badge.expires_at {
+        require!(now < expires_at, BadgeRewardsError::BadgeExpired);
+    }
+    
+    // Promote to the highest tier the user's points reach, skipping any in between
+    require!(ctx.accounts.user_points.user == ctx.accounts.user.key(), BadgeRewardsError::Unauthorized);
+    let tier = ctx.accounts.badge.tier_for(ctx.accounts.user_points.points);
+    require!(tier > ctx.accounts.user_badge.tier, BadgeRewardsError::NoTierUpgrade);
+    ctx.accounts.user_badge.tier = tier;
+    
+    emit_cpi!(BadgeUpgraded {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        user: ctx.accounts.user.key(),
+        tier,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+	badge_id: u64,
+)]
+pub struct WithdrawBadgeYield<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"badge",
+			badge.owner.as_ref(),
+			badge_id.to_le_bytes().as_ref(),
+		],
+		bump = badge.bump,
+		constraint = badge.version == BADGE_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub badge: Account<'info, Badge>,
+
+	pub owner: Signer<'info>,
+
+	/// The token mint yield is paid in
+	#[account(
+		constraint = badge.yield_mint == Some(yield_mint.key()) @ BadgeRewardsError::YieldMintMismatch,
+	)]
+	pub yield_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		token::mint = yield_mint,
+		token::authority = owner,
+	)]
+	pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"yield_vault",
+			badge.key().as_ref(),
+		],
+		bump,
+		token::mint = yield_mint,
+	)]
+	pub yield_vault: InterfaceAccount<'info, TokenAccount>,
+
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw tokens from a badge's yield vault back to the owner
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` badge: [Badge] 
+/// 3. `[signer]` owner: [AccountInfo] The owner of the badge
+/// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+/// 5. `[writable]` owner_token_account: [TokenAccount] The owner's token account to withdraw to
+/// 6. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+/// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 9. `[]` program: [AccountInfo] Auto-generated, this program
+///
+/// Data:
+/// - badge_id: [u64] The badge ID to withdraw yield from
+/// - amount: [u64] Number of tokens to withdraw
+pub fn handler(
+	ctx: Context<WithdrawBadgeYield>,
+	badge_id: u64,
+	amount: u64,
+) -> Result<()> {
+    // Validate that the badge exists and the signer is its authority
+    require!(ctx.accounts.badge.authority == ctx.accounts.owner.key(), BadgeRewardsError::Unauthorized);
+    require!(ctx.accounts.badge.badge_id == badge_id, BadgeRewardsError::InvalidBadgeId);
+    
+    // Holders are paid from the same vault, so the yield accrued to them and not yet paid stays behind
+    require!(amount > 0, BadgeRewardsError::InvalidAmount);
+    ctx.accounts.badge.accrue_yield(Clock::get()?.unix_timestamp)?;
+    let available = ctx.accounts.yield_vault.amount.saturating_sub(ctx.accounts.badge.reserved_yield);
+    require!(amount <= available, BadgeRewardsError::InsufficientVaultBalance);
+    pay_yield(
+        &ctx.accounts.badge,
+        &ctx.accounts.yield_mint,
+        &ctx.accounts.yield_vault,
+        ctx.accounts.owner_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    
+    ctx.accounts.yield_vault.reload()?;
+    emit_cpi!(BadgeYieldWithdrawn {
+        owner: ctx.accounts.badge.owner,
+        badge_id,
+        amount,
+        vault_balance: ctx.accounts.yield_vault.amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use crate::*;
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+	#[account(
+		mut,
+	)]
+	pub fee_payer: Signer<'info>,
+
+	#[account(
+		seeds = [
+			b"config",
+		],
+		bump = config.bump,
+		constraint = !config.paused @ BadgeRewardsError::ProgramPaused,
+		constraint = config.version == CONFIG_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub config: Account<'info, Config>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"vesting_escrow",
+			vesting_escrow.reward.as_ref(),
+			user.key().as_ref(),
+		],
+		bump = vesting_escrow.bump,
+		constraint = vesting_escrow.version == VESTING_ESCROW_VERSION @ BadgeRewardsError::UnsupportedAccountVersion,
+	)]
+	pub vesting_escrow: Account<'info, VestingEscrow>,
+
+	#[account(
+		mut,
+		seeds = [
+			b"vesting_vault",
+			vesting_escrow.reward.as_ref(),
+			user.key().as_ref(),
+		],
+		bump,
+		token::mint = reward_mint,
+		token::authority = vesting_escrow,
+	)]
+	pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+	/// CHECK: Revocation record for the user's badge the reward was claimed with; only read if one has been created
+	#[account(
+		seeds = [
+			b"revoked_badge",
+			vesting_escrow.badge.as_ref(),
+			vesting_escrow.user.as_ref(),
+		],
+		bump,
+	)]
+	pub revoked_badge: UncheckedAccount<'info>,
+
+	/// The token mint held by the vesting vault
+	#[account(
+		address = vesting_escrow.mint @ BadgeRewardsError::InvalidTokenMint,
+	)]
+	pub reward_mint: InterfaceAccount<'info, Mint>,
+
+	#[account(
+		mut,
+		token::mint = reward_mint,
+		token::authority = user,
+	)]
+	pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+	pub user: Signer<'info>,
+
+	pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw the unlocked part of a vesting token reward
+///
+/// Accounts:
+/// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+/// 1. `[]` config: [Config] Global program config, rejected while paused
+/// 2. `[writable]` vesting_escrow: [VestingEscrow] 
+/// 3. `[writable]` vesting_vault: [TokenAccount] Token account holding the escrowed tokens
+/// 4. `[]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+/// 5. `[]` reward_mint: [Mint] The token mint held by the vesting vault
+/// 6. `[writable]` user_token_account: [TokenAccount] The user's token account to withdraw to
+/// 7. `[signer]` user: [AccountInfo] The user who claimed the reward
+/// 8. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+/// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+/// 10. `[]` program: [AccountInfo] Auto-generated, this program
+pub fn handler(
+	ctx: Context<WithdrawVested>,
+) -> Result<()> {
+    // Vesting stops at the revocation if the badge was revoked after the claim
+    let now = Clock::get()?.unix_timestamp;
+    let revoked_badge = RevokedBadge::load(&ctx.accounts.revoked_badge)?;
+    let vested_at = ctx.accounts.vesting_escrow.vesting_stopped_at(revoked_badge.as_ref(), now);
+    
+    // Validate that something has unlocked since the last withdrawal
+    let vesting_escrow = &ctx.accounts.vesting_escrow;
+    let amount = vesting_escrow.vested_amount(vested_at).saturating_sub(vesting_escrow.withdrawn_amount);
+    require!(amount > 0, BadgeRewardsError::NothingVested);
+    
+    // Gross up for any Token-2022 transfer fee so the user receives what unlocked; the final
+    // withdrawal empties the vault, absorbing any rounding left over from earlier ones
+    let withdrawn_amount = vesting_escrow.withdrawn_amount.saturating_add(amount);
+    let transfer_amount = if withdrawn_amount >= vesting_escrow.total_amount {
+        ctx.accounts.vesting_vault.amount
+    } else {
+        pre_fee_amount(&ctx.accounts.reward_mint.to_account_info(), amount)?.min(ctx.accounts.vesting_vault.amount)
+    };
+    
+    // Transfer the unlocked tokens to the user, signed by the vesting escrow PDA
+    let reward_key = vesting_escrow.reward;
+    let user_key = vesting_escrow.user;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vesting_escrow",
+        reward_key.as_ref(),
+        user_key.as_ref(),
+        &[vesting_escrow.bump],
+    ]];
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: ctx.accounts.vesting_vault.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.vesting_escrow.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    anchor_spl::token_interface::transfer_checked(cpi_ctx, transfer_amount, ctx.accounts.reward_mint.decimals)?;
+    
+    ctx.accounts.vesting_escrow.withdrawn_amount = withdrawn_amount;
+    
+    emit_cpi!(VestedWithdrawn {
+        user: user_key,
+        reward: reward_key,
+        amount,
+        withdrawn_amount: ctx.accounts.vesting_escrow.withdrawn_amount,
+        total_amount: ctx.accounts.vesting_escrow.total_amount,
+        timestamp: now,
+    });
+    
+    Ok(())
+}
\ No newline at end of file
@@ -1,15 +1,23 @@
+// `#[program]` emits its IDL handlers (`__private::__idl`) at the crate root and
+// they still call the deprecated `AccountInfo::realloc`; an attribute on the
+// program module does not reach them, so this is the narrowest scope available.
+// Do not rely on it for code in this crate.
+#![allow(deprecated)]
+#![allow(clippy::too_many_arguments)]
+
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
 pub use constants::*;
 pub use instructions::*;
 pub use state::*;
 pub use error::*;
+pub use events::*;
 
 declare_id!("Cw4KGKQcW3Y2C2h299p8h45ETKmtVipVsm7ZkDtEsEQR");
 
@@ -21,9 +29,13 @@ pub mod badge_rewards {
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` badge: [Badge] 
-    /// 2. `[signer]` owner: [AccountInfo] The owner of the badge
-    /// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[]` closed_badge: [ClosedBadge] Record left by closing an earlier badge at this address, may be uninitialized
+    /// 4. `[signer]` owner: [AccountInfo] The owner of the badge
+    /// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 7. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] Unique identifier for this badge
@@ -33,16 +45,24 @@ pub mod badge_rewards {
     /// - required_points: [u64] Points required to earn this badge
     /// - max_earnings: [u64] Maximum number of times this badge can be earned
     /// - is_active: [bool] Whether this badge is currently available for earning
-    pub fn initialize_badge(ctx: Context<InitializeBadge>, badge_id: u64, name: String, description: String, icon_uri: String, required_points: u64, max_earnings: u64, is_active: bool) -> Result<()> {
-        initialize_badge::handler(ctx, badge_id, name, description, icon_uri, required_points, max_earnings, is_active)
+    /// - mint_nft: [bool] Whether earning this badge also mints a soulbound NFT to the user
+    /// - valid_for_seconds: [Option<u64>] How long the badge stays valid once earned, or None if it never expires
+    /// - tiers: [Vec<BadgeTier>] Upgrade levels above the base badge, ordered by increasing required_points
+    /// - prerequisites: [Vec<BadgePrerequisite>] Badges the user must already hold to earn this one
+    pub fn initialize_badge(ctx: Context<InitializeBadge>, badge_id: u64, name: String, description: String, icon_uri: String, required_points: u64, max_earnings: u64, is_active: bool, mint_nft: bool, valid_for_seconds: Option<u64>, tiers: Vec<BadgeTier>, prerequisites: Vec<BadgePrerequisite>) -> Result<()> {
+        initialize_badge::handler(ctx, badge_id, name, description, icon_uri, required_points, max_earnings, is_active, mint_nft, valid_for_seconds, tiers, prerequisites)
     }
 
     /// Update badge information
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` badge: [Badge] 
-    /// 2. `[signer]` owner: [AccountInfo] The owner of the badge
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[signer]` owner: [AccountInfo] The owner of the badge
+    /// 4. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+    /// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 6. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] Unique identifier for this badge
@@ -52,17 +72,27 @@ pub mod badge_rewards {
     /// - required_points: [Option<u64>] Points required to earn this badge
     /// - max_earnings: [Option<u64>] Maximum number of times this badge can be earned
     /// - is_active: [Option<bool>] Whether this badge is currently available for earning
-    pub fn update_badge(ctx: Context<UpdateBadge>, badge_id: u64, name: Option<String>, description: Option<String>, icon_uri: Option<String>, required_points: Option<u64>, max_earnings: Option<u64>, is_active: Option<bool>) -> Result<()> {
-        update_badge::handler(ctx, badge_id, name, description, icon_uri, required_points, max_earnings, is_active)
+    /// - tiers: [Option<Vec<BadgeTier>>] Upgrade levels above the base badge, ordered by increasing required_points
+    /// - prerequisites: [Option<Vec<BadgePrerequisite>>] Badges the user must already hold to earn this one
+    pub fn update_badge(ctx: Context<UpdateBadge>, badge_id: u64, name: Option<String>, description: Option<String>, icon_uri: Option<String>, required_points: Option<u64>, max_earnings: Option<u64>, is_active: Option<bool>, tiers: Option<Vec<BadgeTier>>, prerequisites: Option<Vec<BadgePrerequisite>>) -> Result<()> {
+        update_badge::handler(ctx, badge_id, name, description, icon_uri, required_points, max_earnings, is_active, tiers, prerequisites)
     }
 
     /// Initialize a new reward for a badge
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` reward: [Reward] 
-    /// 2. `[signer]` owner: [AccountInfo] The owner of the reward
-    /// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` badge: [Badge] The badge the reward is for
+    /// 3. `[writable]` reward: [Reward] 
+    /// 4. `[signer]` authority: [AccountInfo] The authority of the badge
+    /// 5. `[optional]` reward_mint: [Mint] The NFT mint, required for NFT rewards
+    /// 6. `[writable, optional]` authority_token_account: [TokenAccount] The authority's token account holding the NFT
+    /// 7. `[writable, optional]` reward_vault: [TokenAccount] Program-owned vault the NFT is escrowed in
+    /// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 9. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 10. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 11. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] The badge ID this reward is associated with
@@ -74,16 +104,27 @@ pub mod badge_rewards {
     /// - token_mint: [Option<Pubkey>] Token mint for token rewards
     /// - nft_mint: [Option<Pubkey>] NFT mint for NFT rewards
     /// - is_active: [bool] Whether this reward is currently available
-    pub fn initialize_reward(ctx: Context<InitializeReward>, badge_id: u64, reward_id: u64, name: String, description: String, reward_type: u8, reward_value: u64, token_mint: Option<Pubkey>, nft_mint: Option<Pubkey>, is_active: bool) -> Result<()> {
-        initialize_reward::handler(ctx, badge_id, reward_id, name, description, reward_type, reward_value, token_mint, nft_mint, is_active)
+    /// - min_tier: [u8] Lowest badge tier that may claim this reward, 0 being the base badge
+    /// - max_claims: [Option<u64>] How many users may claim this reward, or None if unlimited
+    /// - claim_start: [Option<i64>] When claims open, or None to open immediately
+    /// - claim_end: [Option<i64>] When claims close, or None to never close
+    /// - vesting: [Option<VestingSchedule>] Cliff and duration over which token claims unlock, or None to pay out at once
+    pub fn initialize_reward(ctx: Context<InitializeReward>, badge_id: u64, reward_id: u64, name: String, description: String, reward_type: u8, reward_value: u64, token_mint: Option<Pubkey>, nft_mint: Option<Pubkey>, is_active: bool, min_tier: u8, max_claims: Option<u64>, claim_start: Option<i64>, claim_end: Option<i64>, vesting: Option<VestingSchedule>) -> Result<()> {
+        initialize_reward::handler(ctx, badge_id, reward_id, name, description, reward_type, reward_value, token_mint, nft_mint, is_active, min_tier, max_claims, claim_start, claim_end, vesting)
     }
 
     /// Update reward information
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` reward: [Reward] 
-    /// 2. `[signer]` owner: [AccountInfo] The owner of the reward
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` badge: [Badge] The badge the reward is for
+    /// 3. `[writable]` reward: [Reward] 
+    /// 4. `[]` reward_vault: [TokenAccount] The reward vault, may be uninitialized
+    /// 5. `[signer]` owner: [AccountInfo] The owner of the reward
+    /// 6. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+    /// 7. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 8. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] The badge ID this reward is associated with
@@ -95,19 +136,36 @@ pub mod badge_rewards {
     /// - token_mint: [Option<Pubkey>] Token mint for token rewards
     /// - nft_mint: [Option<Pubkey>] NFT mint for NFT rewards
     /// - is_active: [Option<bool>] Whether this reward is currently available
-    pub fn update_reward(ctx: Context<UpdateReward>, badge_id: u64, reward_id: u64, name: Option<String>, description: Option<String>, reward_type: Option<u8>, reward_value: Option<u64>, token_mint: Option<Pubkey>, nft_mint: Option<Pubkey>, is_active: Option<bool>) -> Result<()> {
-        update_reward::handler(ctx, badge_id, reward_id, name, description, reward_type, reward_value, token_mint, nft_mint, is_active)
+    /// - min_tier: [Option<u8>] Lowest badge tier that may claim this reward, 0 being the base badge
+    /// - max_claims: [Option<Option<u64>>] New cap on how many users may claim this reward, or Some(None) to remove the cap
+    /// - claim_start: [Option<Option<i64>>] New time claims open, or Some(None) to open them immediately
+    /// - claim_end: [Option<Option<i64>>] New time claims close, or Some(None) to never close them
+    /// - vesting: [Option<Option<VestingSchedule>>] New vesting schedule for future claims, or Some(None) to pay them out immediately
+    pub fn update_reward(ctx: Context<UpdateReward>, badge_id: u64, reward_id: u64, name: Option<String>, description: Option<String>, reward_type: Option<u8>, reward_value: Option<u64>, token_mint: Option<Pubkey>, nft_mint: Option<Pubkey>, is_active: Option<bool>, min_tier: Option<u8>, max_claims: Option<Option<u64>>, claim_start: Option<Option<i64>>, claim_end: Option<Option<i64>>, vesting: Option<Option<VestingSchedule>>) -> Result<()> {
+        update_reward::handler(ctx, badge_id, reward_id, name, description, reward_type, reward_value, token_mint, nft_mint, is_active, min_tier, max_claims, claim_start, claim_end, vesting)
     }
 
     /// Stake a badge (mint it to user)
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` badge: [Badge] 
-    /// 2. `[writable]` user_badge: [UserBadge] 
-    /// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-    /// 4. `[signer]` user: [AccountInfo] The user staking the badge
-    /// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[writable]` user_badge: [UserBadge] 
+    /// 4. `[writable]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+    /// 5. `[]` user_points: [UserPoints] The user's points balance with the badge owner
+    /// 6. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+    /// 7. `[signer]` user: [AccountInfo] The user staking the badge
+    /// 8. `[writable, optional]` badge_mint: [Mint] Soulbound NFT mint, required if the badge mints NFTs
+    /// 9. `[writable, optional]` user_token_account: [TokenAccount] The user's associated token account for the badge_mint
+    /// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 11. `[optional]` token_program: [AccountInfo] Token-2022 program, required if the badge mints NFTs
+    /// 12. `[optional]` associated_token_program: [AccountInfo] Associated token program, required if the badge mints NFTs
+    /// 13. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 14. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Remaining accounts:
+    /// 0..n. `[]` user_badges: [UserBadge] The user's user_badge for each of the badge's prerequisites, in order
     ///
     /// Data:
     /// - badge_id: [u64] The badge ID to stake
@@ -119,11 +177,22 @@ pub mod badge_rewards {
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` badge: [Badge] 
-    /// 2. `[writable]` user_badge: [UserBadge] 
-    /// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-    /// 4. `[signer]` user: [AccountInfo] The user unstaking the badge
-    /// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[writable]` user_badge: [UserBadge] 
+    /// 4. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+    /// 5. `[signer]` user: [AccountInfo] The user unstaking the badge
+    /// 6. `[writable]` rent_recipient: [AccountInfo] The account that funded the user_badge, receives its rent
+    /// 7. `[writable, optional]` badge_mint: [Mint] Soulbound NFT mint, required if one was minted for the user_badge
+    /// 8. `[writable, optional]` user_token_account: [TokenAccount] The user's token account holding the badge NFT
+    /// 9. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 10. `[optional]` token_program: [AccountInfo] Token-2022 program, required if one was minted for the user_badge
+    /// 11. `[optional]` yield_mint: [Mint] The token mint yield is paid in; without the yield accounts unharvested yield is forfeited
+    /// 12. `[writable, optional]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+    /// 13. `[writable, optional]` user_yield_account: [TokenAccount] The user's token account to pay unharvested yield to, up to the vault balance
+    /// 14. `[optional]` yield_token_program: [AccountInfo] Token program of the yield mint
+    /// 15. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 16. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] The badge ID to unstake
@@ -135,9 +204,12 @@ pub mod badge_rewards {
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` badge: [Badge] 
-    /// 2. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-    /// 3. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+    /// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 6. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] The badge ID to freeze
@@ -149,36 +221,53 @@ pub mod badge_rewards {
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[writable]` badge: [Badge] 
-    /// 2. `[writable]` user_badge: [UserBadge] 
-    /// 3. `[signer]` badge_owner: [AccountInfo] The owner of the badge
-    /// 4. `[signer]` user: [AccountInfo] The user to revoke the badge from
-    /// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[writable]` user_badge: [UserBadge] 
+    /// 4. `[writable]` revoked_badge: [RevokedBadge] Revocation record for the badge and user
+    /// 5. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+    /// 6. `[]` user: [AccountInfo] The user to revoke the badge from
+    /// 7. `[writable]` rent_recipient: [AccountInfo] The account that funded the user_badge, receives its rent
+    /// 8. `[writable, optional]` badge_mint: [Mint] Soulbound NFT mint, required if one was minted for the user_badge
+    /// 9. `[writable, optional]` user_token_account: [TokenAccount] The user's token account holding the badge NFT
+    /// 10. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 11. `[optional]` token_program: [AccountInfo] Token-2022 program, required if one was minted for the user_badge
+    /// 12. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 13. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - badge_id: [u64] The badge ID to revoke
-    pub fn revoke_badge(ctx: Context<RevokeBadge>, badge_id: u64) -> Result<()> {
-        revoke_badge::handler(ctx, badge_id)
+    /// - reason_code: [u8] Why the badge was revoked (see REVOKE_REASON_* constants)
+    /// - evidence_uri: [Option<String>] URI to supporting evidence for the revocation
+    /// - allow_reissue: [bool] Whether the badge may be issued to this user again
+    pub fn revoke_badge(ctx: Context<RevokeBadge>, badge_id: u64, reason_code: u8, evidence_uri: Option<String>, allow_reissue: bool) -> Result<()> {
+        revoke_badge::handler(ctx, badge_id, reason_code, evidence_uri, allow_reissue)
     }
 
     /// Claim a reward for a badge
     ///
     /// Accounts:
     /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
-    /// 1. `[signer]` user: [AccountInfo] The user claiming the reward
-    /// 2. `[writable]` user_reward: [UserReward] 
-    /// 3. `[writable]` reward: [Reward] 
-    /// 4. `[writable]` badge: [Badge] 
-    /// 5. `[writable]` user_badge: [UserBadge] 
-    /// 6. `[writable]` user_token_account: [AccountInfo] User's token account for reward transfer
-    /// 7. `[writable]` reward_token_account: [AccountInfo] Reward token account for transfer
-    /// 8. `[]` reward_mint: [Mint] The token mint for the reward
-    /// 9. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
-    /// 10. `[writable]` source: [AccountInfo] The source account.
-    /// 11. `[]` mint: [Mint] The token mint.
-    /// 12. `[writable]` destination: [AccountInfo] The destination account.
-    /// 13. `[signer]` authority: [AccountInfo] The source account's owner/delegate.
-    /// 14. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[signer]` user: [AccountInfo] The user claiming the reward
+    /// 3. `[writable]` user_reward: [UserReward] 
+    /// 4. `[writable]` reward: [Reward] 
+    /// 5. `[writable]` badge: [Badge] 
+    /// 6. `[writable]` user_badge: [UserBadge] The user's badge, closed if it was revoked
+    /// 7. `[]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+    /// 8. `[writable, optional]` user_token_account: [AccountInfo] User's token account for reward transfer
+    /// 9. `[writable, optional]` reward_token_account: [AccountInfo] The reward vault, required for token and NFT rewards
+    /// 10. `[optional]` reward_mint: [Mint] The token mint for the reward
+    /// 11. `[writable, optional]` vesting_escrow: [VestingEscrow] The user's vesting escrow, required if the reward vests
+    /// 12. `[writable, optional]` vesting_vault: [TokenAccount] Token account holding the escrowed tokens, required if the reward vests
+    /// 13. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 14. `[writable]` source: [AccountInfo] The source account.
+    /// 15. `[]` mint: [Mint] The token mint.
+    /// 16. `[writable]` destination: [AccountInfo] The destination account.
+    /// 17. `[signer]` authority: [AccountInfo] The source account's owner/delegate.
+    /// 18. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 19. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 20. `[]` program: [AccountInfo] Auto-generated, this program
     ///
     /// Data:
     /// - owner: [Pubkey] The owner of the badge
@@ -193,22 +282,615 @@ pub mod badge_rewards {
     /// Get all badges earned by a user
     ///
     /// Accounts:
-    /// 0. `[writable, signer]` fee_payer: [AccountInfo] Auto-generated, default fee payer
+    /// 0..n. `[]` remaining_accounts: [UserBadge] Candidate user_badge accounts for the user
     ///
     /// Data:
     /// - user: [Pubkey] The user to get badges for
-    pub fn get_user_badges(ctx: Context<GetUserBadges>, user: Pubkey) -> Result<()> {
+    ///
+    /// Returns the held badges as a Borsh-encoded [Vec<UserBadgeSummary>] in the
+    /// transaction return data. Candidates that have been closed are skipped.
+    pub fn get_user_badges(ctx: Context<GetUserBadges>, user: Pubkey) -> Result<Vec<UserBadgeSummary>> {
         get_user_badges::handler(ctx, user)
     }
 
     /// Get all rewards claimed by a user
     ///
     /// Accounts:
-    /// 0. `[writable, signer]` fee_payer: [AccountInfo] Auto-generated, default fee payer
+    /// 0..n. `[]` remaining_accounts: [UserReward] Candidate user_reward accounts for the user
     ///
     /// Data:
     /// - user: [Pubkey] The user to get rewards for
-    pub fn get_user_rewards(ctx: Context<GetUserRewards>, user: Pubkey) -> Result<()> {
+    ///
+    /// Returns the claimed rewards as a Borsh-encoded [Vec<UserRewardSummary>] in the
+    /// transaction return data. Candidates that have been closed are skipped.
+    pub fn get_user_rewards(ctx: Context<GetUserRewards>, user: Pubkey) -> Result<Vec<UserRewardSummary>> {
         get_user_rewards::handler(ctx, user)
     }
+
+    /// Award points to a user
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` user_points: [UserPoints] 
+    /// 3. `[signer]` owner: [AccountInfo] The badge owner awarding the points
+    /// 4. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 6. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - user: [Pubkey] The user receiving the points
+    /// - amount: [u64] Number of points to award
+    pub fn award_points(ctx: Context<AwardPoints>, user: Pubkey, amount: u64) -> Result<()> {
+        award_points::handler(ctx, user, amount)
+    }
+
+    /// Deduct points from a user
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` user_points: [UserPoints] 
+    /// 3. `[signer]` owner: [AccountInfo] The badge owner deducting the points
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - user: [Pubkey] The user losing the points
+    /// - amount: [u64] Number of points to deduct
+    pub fn deduct_points(ctx: Context<DeductPoints>, user: Pubkey, amount: u64) -> Result<()> {
+        deduct_points::handler(ctx, user, amount)
+    }
+
+    /// Deposit tokens into a reward's vault
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` reward: [Reward] 
+    /// 3. `[signer]` owner: [AccountInfo] The owner of the reward
+    /// 4. `[]` reward_mint: [Mint] The token mint for the reward
+    /// 5. `[writable]` owner_token_account: [TokenAccount] The owner's token account to fund from
+    /// 6. `[writable]` reward_vault: [TokenAccount] Program-owned vault claims are paid from
+    /// 7. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 8. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 10. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward is associated with
+    /// - reward_id: [u64] The reward ID
+    /// - amount: [u64] Number of tokens to deposit
+    pub fn fund_reward(ctx: Context<FundReward>, badge_id: u64, reward_id: u64, amount: u64) -> Result<()> {
+        fund_reward::handler(ctx, badge_id, reward_id, amount)
+    }
+
+    /// Withdraw tokens from a reward's vault back to the owner
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` reward: [Reward] 
+    /// 3. `[signer]` owner: [AccountInfo] The owner of the reward
+    /// 4. `[]` reward_mint: [Mint] The token mint held by the reward vault
+    /// 5. `[writable]` owner_token_account: [TokenAccount] The owner's token account to withdraw to
+    /// 6. `[writable]` reward_vault: [TokenAccount] Program-owned vault claims are paid from
+    /// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward is associated with
+    /// - reward_id: [u64] The reward ID
+    /// - amount: [u64] Number of tokens to withdraw
+    pub fn withdraw_reward(ctx: Context<WithdrawReward>, badge_id: u64, reward_id: u64, amount: u64) -> Result<()> {
+        withdraw_reward::handler(ctx, badge_id, reward_id, amount)
+    }
+
+    /// Stake a reward to hold it back from claiming (business user)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` reward: [Reward] 
+    /// 3. `[signer]` business_owner: [AccountInfo] The owner of the reward
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward is associated with
+    /// - reward_id: [u64] The reward ID to stake
+    /// - min_stake_period: [u64] Minimum seconds to keep the reward staked (0 = no minimum)
+    pub fn stake_reward(ctx: Context<StakeReward>, badge_id: u64, reward_id: u64, min_stake_period: u64) -> Result<()> {
+        stake_reward::handler(ctx, badge_id, reward_id, min_stake_period)
+    }
+
+    /// Unstake a reward to make it available for claiming (business user)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` reward: [Reward] 
+    /// 3. `[signer]` business_owner: [AccountInfo] The owner of the reward
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward is associated with
+    /// - reward_id: [u64] The reward ID to unstake
+    pub fn unstake_reward(ctx: Context<UnstakeReward>, badge_id: u64, reward_id: u64) -> Result<()> {
+        unstake_reward::handler(ctx, badge_id, reward_id)
+    }
+
+    /// Lock a claimed reward for a minimum period (player)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` reward: [Reward] 
+    /// 3. `[writable]` user_reward: [UserReward] 
+    /// 4. `[signer]` player: [AccountInfo] The player who claimed the reward
+    /// 5. `[optional]` reward_mint: [Mint] The token mint, required for token and NFT rewards
+    /// 6. `[writable, optional]` player_token_account: [TokenAccount] The player's token account holding the reward
+    /// 7. `[writable, optional]` reward_escrow: [TokenAccount] Escrow the reward is locked in
+    /// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 9. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 10. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 11. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - owner: [Pubkey] The owner of the reward
+    /// - badge_id: [u64] The badge ID the reward is associated with
+    /// - reward_id: [u64] The reward ID to lock
+    /// - min_stake_period: [u64] Minimum seconds to keep the reward locked (0 = no minimum)
+    pub fn stake_reward_player(ctx: Context<StakeRewardPlayer>, owner: Pubkey, badge_id: u64, reward_id: u64, min_stake_period: u64) -> Result<()> {
+        stake_reward_player::handler(ctx, owner, badge_id, reward_id, min_stake_period)
+    }
+
+    /// Unlock a claimed reward after its minimum period (player)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` user_reward: [UserReward] 
+    /// 3. `[writable, signer]` player: [AccountInfo] The player who locked the reward, receives the escrow rent
+    /// 4. `[writable, optional]` reward_mint: [Mint] The token mint held in escrow, receives the escrow's withheld transfer fees
+    /// 5. `[writable, optional]` player_token_account: [TokenAccount] The player's token account to release to
+    /// 6. `[writable, optional]` reward_escrow: [TokenAccount] Escrow the reward is locked in
+    /// 7. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - reward_id: [u64] The reward ID to unlock
+    pub fn unstake_reward_player(ctx: Context<UnstakeRewardPlayer>, reward_id: u64) -> Result<()> {
+        unstake_reward_player::handler(ctx, reward_id)
+    }
+
+    /// Initialize the global program config
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [Config] 
+    /// 2. `[signer]` admin: [AccountInfo] The initial admin, must be the program's upgrade authority
+    /// 3. `[]` badge_rewards_program: [AccountInfo] This program, used to locate its program data
+    /// 4. `[]` program_data: [ProgramData] This program's upgradeable loader data
+    /// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 7. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - protocol_fee_bps: [u16] Protocol fee in basis points
+    pub fn initialize_config(ctx: Context<InitializeConfig>, protocol_fee_bps: u16) -> Result<()> {
+        initialize_config::handler(ctx, protocol_fee_bps)
+    }
+
+    /// Pause or resume all mutating instructions (admin)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [Config] 
+    /// 2. `[signer]` admin: [AccountInfo] The config admin
+    /// 3. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 4. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - paused: [bool] Whether the program should be paused
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        set_pause::handler(ctx, paused)
+    }
+
+    /// Hand the config admin role to a new key (admin)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [Config] 
+    /// 2. `[signer]` admin: [AccountInfo] The current config admin
+    /// 3. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 4. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - new_admin: [Pubkey] The key that becomes admin
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        transfer_admin::handler(ctx, new_admin)
+    }
+
+    /// Change the protocol fee (admin)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[writable]` config: [Config] 
+    /// 2. `[signer]` admin: [AccountInfo] The config admin
+    /// 3. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 4. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - protocol_fee_bps: [u16] Protocol fee in basis points
+    pub fn set_protocol_fee(ctx: Context<SetProtocolFee>, protocol_fee_bps: u16) -> Result<()> {
+        set_protocol_fee::handler(ctx, protocol_fee_bps)
+    }
+
+    /// Propose a new owner for a badge, or cancel a pending proposal
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[signer]` authority: [AccountInfo] The current authority of the badge
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to transfer
+    /// - new_authority: [Option<Pubkey>] The proposed owner, or None to cancel the proposal
+    pub fn propose_badge_owner(ctx: Context<ProposeBadgeOwner>, badge_id: u64, new_authority: Option<Pubkey>) -> Result<()> {
+        propose_badge_owner::handler(ctx, badge_id, new_authority)
+    }
+
+    /// Accept a pending ownership proposal for a badge
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[signer]` new_authority: [AccountInfo] The proposed owner taking over the badge
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to accept
+    pub fn accept_badge_owner(ctx: Context<AcceptBadgeOwner>, badge_id: u64) -> Result<()> {
+        accept_badge_owner::handler(ctx, badge_id)
+    }
+
+    /// Propose a new owner for a reward, or cancel a pending proposal
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` reward: [Reward] 
+    /// 3. `[signer]` authority: [AccountInfo] The current authority of the reward
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward belongs to
+    /// - reward_id: [u64] The reward ID to transfer
+    /// - new_authority: [Option<Pubkey>] The proposed owner, or None to cancel the proposal
+    pub fn propose_reward_owner(ctx: Context<ProposeRewardOwner>, badge_id: u64, reward_id: u64, new_authority: Option<Pubkey>) -> Result<()> {
+        propose_reward_owner::handler(ctx, badge_id, reward_id, new_authority)
+    }
+
+    /// Accept a pending ownership proposal for a reward
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` reward: [Reward] 
+    /// 3. `[signer]` new_authority: [AccountInfo] The proposed owner taking over the reward
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward belongs to
+    /// - reward_id: [u64] The reward ID to accept
+    pub fn accept_reward_owner(ctx: Context<AcceptRewardOwner>, badge_id: u64, reward_id: u64) -> Result<()> {
+        accept_reward_owner::handler(ctx, badge_id, reward_id)
+    }
+
+    /// Close a retired badge and reclaim its rent
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[writable, signer]` badge_owner: [AccountInfo] The authority of the badge, receives its rent
+    /// 4. `[writable]` closed_badge: [ClosedBadge] Record carrying the badge's issued count over to a re-created badge
+    /// 5. `[writable, optional]` yield_mint: [Mint] The token mint yield is paid in, receives the yield vault's withheld transfer fees
+    /// 6. `[writable, optional]` yield_vault: [TokenAccount] The badge's yield vault, required if a yield mint was set
+    /// 7. `[optional]` token_program: [AccountInfo] Token program of the yield vault, required if a yield mint was set
+    /// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 10. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to close
+    pub fn close_badge(ctx: Context<CloseBadge>, badge_id: u64) -> Result<()> {
+        close_badge::handler(ctx, badge_id)
+    }
+
+    /// Close a retired reward, optionally sweeping its vault back to the owner
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` reward: [Reward] 
+    /// 3. `[writable, signer]` owner: [AccountInfo] The authority of the reward, receives the rent
+    /// 4. `[writable, optional]` reward_vault: [TokenAccount] The reward vault, required for token and NFT rewards
+    /// 5. `[writable, optional]` reward_mint: [Mint] The token mint held by the reward vault, required to sweep it or harvest its withheld transfer fees
+    /// 6. `[writable, optional]` owner_token_account: [TokenAccount] The owner's token account to sweep the vault to
+    /// 7. `[optional]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward is associated with
+    /// - reward_id: [u64] The reward ID to close
+    /// - sweep: [bool] Whether to return any tokens left in the vault to the owner
+    pub fn close_reward(ctx: Context<CloseReward>, badge_id: u64, reward_id: u64, sweep: bool) -> Result<()> {
+        close_reward::handler(ctx, badge_id, reward_id, sweep)
+    }
+
+    /// Upgrade an account to the current layout of its type (permissionless)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] Pays for any extra rent the new layout needs
+    /// 1. `[]` config: [Config] Global program config in any layout version, rejected while paused
+    /// 2. `[writable]` account: [AccountInfo] The Badge, Reward, UserBadge, UserReward, UserPoints, RevokedBadge, Config, Collection or VestingEscrow to migrate
+    /// 3. `[]` system_program: [AccountInfo] Auto-generated, for account reallocation
+    /// 4. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 5. `[]` program: [AccountInfo] Auto-generated, this program
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        migrate_account::handler(ctx)
+    }
+
+    /// Bind a migrated user badge to the badge it was issued from
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` badge: [Badge] The badge the user badge was issued from
+    /// 3. `[writable]` user_badge: [UserBadge] A migrated user badge not yet bound to a badge
+    /// 4. `[signer]` authority: [AccountInfo] The badge's authority
+    /// 5. `[signer, optional]` user_signer: [AccountInfo] The user, required to co-sign if the user badge has no soulbound NFT
+    /// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 7. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID the user badge was issued from
+    /// - user: [Pubkey] The user holding the badge
+    pub fn backfill_user_badge(ctx: Context<BackfillUserBadge>, badge_id: u64, user: Pubkey) -> Result<()> {
+        backfill_user_badge::handler(ctx, badge_id, user)
+    }
+
+    /// Extend a user's badge validity without re-issuing it
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` badge: [Badge] 
+    /// 3. `[writable]` user_badge: [UserBadge] 
+    /// 4. `[signer]` badge_owner: [AccountInfo] The owner of the badge
+    /// 5. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 6. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to renew
+    /// - user: [Pubkey] The user holding the badge
+    pub fn renew_badge(ctx: Context<RenewBadge>, badge_id: u64, user: Pubkey) -> Result<()> {
+        renew_badge::handler(ctx, badge_id, user)
+    }
+
+    /// Promote a held badge to the highest tier the user's points qualify for
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` badge: [Badge] 
+    /// 3. `[writable]` user_badge: [UserBadge] 
+    /// 4. `[]` user_points: [UserPoints] The user's points balance with the badge owner
+    /// 5. `[signer]` user: [AccountInfo] The user holding the badge
+    /// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 7. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to upgrade
+    pub fn upgrade_badge(ctx: Context<UpgradeBadge>, badge_id: u64) -> Result<()> {
+        upgrade_badge::handler(ctx, badge_id)
+    }
+
+    /// Initialize a collection of badges that awards a meta-badge once a user holds them all
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` meta_badge: [Badge] The badge awarded on completion
+    /// 3. `[writable]` collection: [Collection] 
+    /// 4. `[signer]` owner: [AccountInfo] The owner of the badges
+    /// 5. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 6. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 7. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - collection_id: [u64] Unique identifier for this collection
+    /// - name: [String] Name of the collection
+    /// - badge_ids: [Vec<u64>] The owner's badges a user must hold to complete the collection
+    /// - meta_badge_id: [u64] The owner's badge awarded on completion
+    pub fn initialize_collection(ctx: Context<InitializeCollection>, collection_id: u64, name: String, badge_ids: Vec<u64>, meta_badge_id: u64) -> Result<()> {
+        initialize_collection::handler(ctx, collection_id, name, badge_ids, meta_badge_id)
+    }
+
+    /// Award a collection's meta-badge to a user holding every member badge, without the owner co-signing
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` collection: [Collection] 
+    /// 3. `[writable]` meta_badge: [Badge] The badge awarded on completion
+    /// 4. `[writable]` user_badge: [UserBadge] The user's meta-badge
+    /// 5. `[writable]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+    /// 6. `[]` user_points: [UserPoints] The user's points balance with the collection owner
+    /// 7. `[signer]` user: [AccountInfo] The user completing the collection
+    /// 8. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 10. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Remaining accounts:
+    /// 0..n. `[]` user_badges: [UserBadge] The user's user_badge for each of the collection's badges, in order
+    /// n..m. `[]` prerequisite_user_badges: [UserBadge] The user's user_badge for each of the meta-badge's prerequisites, in order
+    ///
+    /// Data:
+    /// - collection_id: [u64] The collection to complete
+    pub fn complete_collection(ctx: Context<CompleteCollection>, collection_id: u64) -> Result<()> {
+        complete_collection::handler(ctx, collection_id)
+    }
+
+    /// Return a reward's unclaimed vault balance to its authority once the claim window has closed (permissionless)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` reward: [Reward] 
+    /// 3. `[]` reward_mint: [Mint] The token mint held by the reward vault
+    /// 4. `[writable]` authority_token_account: [TokenAccount] The reward authority's token account to return the balance to
+    /// 5. `[writable]` reward_vault: [TokenAccount] Program-owned vault claims are paid from
+    /// 6. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 7. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 8. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID this reward is associated with
+    /// - reward_id: [u64] The reward ID
+    pub fn sweep_expired_reward(ctx: Context<SweepExpiredReward>, badge_id: u64, reward_id: u64) -> Result<()> {
+        sweep_expired_reward::handler(ctx, badge_id, reward_id)
+    }
+
+    /// Withdraw the unlocked part of a vesting token reward
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` vesting_escrow: [VestingEscrow] 
+    /// 3. `[writable]` vesting_vault: [TokenAccount] Token account holding the escrowed tokens
+    /// 4. `[]` revoked_badge: [RevokedBadge] Revocation record for the badge and user, may be uninitialized
+    /// 5. `[]` reward_mint: [Mint] The token mint held by the vesting vault
+    /// 6. `[writable]` user_token_account: [TokenAccount] The user's token account to withdraw to
+    /// 7. `[signer]` user: [AccountInfo] The user who claimed the reward
+    /// 8. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 10. `[]` program: [AccountInfo] Auto-generated, this program
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        withdraw_vested::handler(ctx)
+    }
+
+    /// Return the unvested part of a vesting token reward to the reward vault after the badge was revoked (permissionless)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` vesting_escrow: [VestingEscrow] 
+    /// 3. `[writable]` vesting_vault: [TokenAccount] Token account holding the escrowed tokens
+    /// 4. `[]` revoked_badge: [RevokedBadge] Revocation record for the user's badge the reward was claimed with
+    /// 5. `[]` reward_mint: [Mint] The token mint held by the vesting vault
+    /// 6. `[writable]` reward_vault: [TokenAccount] The vault of the reward the tokens were claimed from
+    /// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    pub fn reclaim_unvested(ctx: Context<ReclaimUnvested>) -> Result<()> {
+        reclaim_unvested::handler(ctx)
+    }
+
+    /// Set the rate at which a badge emits yield to its holders
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[signer]` owner: [AccountInfo] The owner of the badge
+    /// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+    /// 5. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+    /// 6. `[]` system_program: [AccountInfo] Auto-generated, for account initialization
+    /// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to set yield for
+    /// - emission_rate: [u64] Yield tokens emitted per second, shared equally among holders, or 0 to stop
+    pub fn set_badge_yield(ctx: Context<SetBadgeYield>, badge_id: u64, emission_rate: u64) -> Result<()> {
+        set_badge_yield::handler(ctx, badge_id, emission_rate)
+    }
+
+    /// Deposit tokens into a badge's yield vault (anyone may fund)
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[]` badge: [Badge] 
+    /// 3. `[signer]` funder: [AccountInfo] The wallet depositing the tokens
+    /// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+    /// 5. `[writable]` funder_token_account: [TokenAccount] The funder's token account to fund from
+    /// 6. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+    /// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to fund yield for
+    /// - amount: [u64] Number of tokens to deposit
+    pub fn fund_badge_yield(ctx: Context<FundBadgeYield>, badge_id: u64, amount: u64) -> Result<()> {
+        fund_badge_yield::handler(ctx, badge_id, amount)
+    }
+
+    /// Pay out the yield a held badge has accrued since it was earned or last harvested
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[writable]` user_badge: [UserBadge] 
+    /// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+    /// 5. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+    /// 6. `[writable]` user_token_account: [TokenAccount] The user's token account to pay the yield to
+    /// 7. `[signer]` user: [AccountInfo] The user holding the badge
+    /// 8. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 9. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 10. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to harvest yield from
+    pub fn harvest(ctx: Context<Harvest>, badge_id: u64) -> Result<()> {
+        harvest::handler(ctx, badge_id)
+    }
+
+    /// Withdraw tokens from a badge's yield vault back to the owner
+    ///
+    /// Accounts:
+    /// 0. `[writable, signer]` fee_payer: [AccountInfo] 
+    /// 1. `[]` config: [Config] Global program config, rejected while paused
+    /// 2. `[writable]` badge: [Badge] 
+    /// 3. `[signer]` owner: [AccountInfo] The owner of the badge
+    /// 4. `[]` yield_mint: [Mint] The token mint yield is paid in
+    /// 5. `[writable]` owner_token_account: [TokenAccount] The owner's token account to withdraw to
+    /// 6. `[writable]` yield_vault: [TokenAccount] Program-owned vault yield is paid from
+    /// 7. `[]` token_program: [AccountInfo] Auto-generated, TokenProgram
+    /// 8. `[]` event_authority: [AccountInfo] Auto-generated, for emitting events through CPI
+    /// 9. `[]` program: [AccountInfo] Auto-generated, this program
+    ///
+    /// Data:
+    /// - badge_id: [u64] The badge ID to withdraw yield from
+    /// - amount: [u64] Number of tokens to withdraw
+    pub fn withdraw_badge_yield(ctx: Context<WithdrawBadgeYield>, badge_id: u64, amount: u64) -> Result<()> {
+        withdraw_badge_yield::handler(ctx, badge_id, amount)
+    }
 }
\ No newline at end of file
@@ -1,15 +1,156 @@
 use anchor_lang::prelude::*;
+use crate::{constants::{MAX_BADGE_PREREQUISITES, MAX_BADGE_TIERS, YIELD_PRECISION}, error::BadgeRewardsError, state::UserBadge};
 
 #[account]
+#[derive(InitSpace)]
 pub struct Badge {
+	/// Layout version, see BADGE_VERSION
+	pub version: u8,
+	/// Issuer that created the badge; part of the PDA seeds and never changes
 	pub owner: Pubkey,
+	/// Wallet currently allowed to manage the badge
+	pub authority: Pubkey,
+	/// Wallet proposed as the next authority, pending its acceptance
+	pub pending_authority: Option<Pubkey>,
 	pub badge_id: u64,
+	#[max_len(100)]
 	pub name: String,
+	#[max_len(500)]
 	pub description: String,
+	#[max_len(200)]
 	pub icon_uri: String,
 	pub required_points: u64,
 	pub max_earnings: u64,
+	/// Number of users currently holding this badge
 	pub total_earned: u64,
+	/// Number of times this badge has ever been issued, capped by max_earnings
+	pub total_issued: u64,
 	pub is_active: bool,
+	/// When the badge was last deactivated, or 0 while active
+	pub deactivated_at: i64,
+	/// Whether stake_badge also mints a soulbound Token-2022 NFT to the user
+	pub mint_nft: bool,
 	pub bump: u8,
+	/// How long the badge stays valid after it is earned or renewed, or None if it never expires
+	pub valid_for_seconds: Option<u64>,
+	/// Upgrade levels above the base badge, ordered by strictly increasing required_points
+	#[max_len(MAX_BADGE_TIERS)]
+	pub tiers: Vec<BadgeTier>,
+	/// Badges a user must already hold, unexpired, before earning this one
+	#[max_len(MAX_BADGE_PREREQUISITES)]
+	pub prerequisites: Vec<BadgePrerequisite>,
+	/// Yield tokens emitted per second, shared equally among current holders, or 0 if the badge yields nothing
+	pub emission_rate: u64,
+	/// Mint of the yield tokens, fixed once set
+	pub yield_mint: Option<Pubkey>,
+	/// Yield accrued per held badge since yield began, scaled by YIELD_PRECISION
+	pub acc_yield_per_badge: u128,
+	/// When acc_yield_per_badge was last brought up to date
+	pub last_yield_update: i64,
+	/// Yield accrued to current holders and not yet paid out or forfeited, kept back from withdrawals
+	pub reserved_yield: u64,
+}
+
+/// A level of a badge above its base level; tier N is `tiers[N - 1]`
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug, PartialEq, Eq)]
+pub struct BadgeTier {
+	pub required_points: u64,
+	#[max_len(200)]
+	pub icon_uri: String,
+}
+
+/// A badge, identified by its owner and badge_id, that must be held before earning another
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BadgePrerequisite {
+	pub owner: Pubkey,
+	pub badge_id: u64,
+}
+
+impl Badge {
+	/// Exact account size for a badge holding the given strings, tiers and prerequisites
+	pub fn space(name: &str, description: &str, icon_uri: &str, tiers: &[BadgeTier], prerequisites: &[BadgePrerequisite]) -> usize {
+		8 + Badge::INIT_SPACE - (100 + 500 + 200) - MAX_BADGE_TIERS * BadgeTier::INIT_SPACE
+			+ name.len() + description.len() + icon_uri.len()
+			+ tiers.iter().map(|tier| 8 + 4 + tier.icon_uri.len()).sum::<usize>()
+			- (MAX_BADGE_PREREQUISITES - prerequisites.len().min(MAX_BADGE_PREREQUISITES)) * BadgePrerequisite::INIT_SPACE
+	}
+
+	/// Validate that the tiers are within limits and each needs more points than the level below
+	pub fn validate_tiers(&self) -> Result<()> {
+		require!(self.tiers.len() <= MAX_BADGE_TIERS, BadgeRewardsError::TooManyTiers);
+		let mut previous_points = self.required_points;
+		for tier in self.tiers.iter() {
+			require!(tier.required_points > previous_points, BadgeRewardsError::InvalidTierPoints);
+			require!(tier.icon_uri.len() <= 200, BadgeRewardsError::IconUriTooLong);
+			previous_points = tier.required_points;
+		}
+		Ok(())
+	}
+
+	/// Validate that the prerequisites are within limits, distinct, and do not include this badge
+	pub fn validate_prerequisites(&self) -> Result<()> {
+		require!(self.prerequisites.len() <= MAX_BADGE_PREREQUISITES, BadgeRewardsError::TooManyPrerequisites);
+		for (index, prerequisite) in self.prerequisites.iter().enumerate() {
+			require!(
+				prerequisite.owner != self.owner || prerequisite.badge_id != self.badge_id,
+				BadgeRewardsError::InvalidPrerequisite
+			);
+			require!(!self.prerequisites[..index].contains(prerequisite), BadgeRewardsError::InvalidPrerequisite);
+		}
+		Ok(())
+	}
+
+	/// Validate that `user` holds every prerequisite at `now`, given their user_badge for each in order
+	pub fn require_prerequisites(&self, user: &Pubkey, user_badges: &[AccountInfo], now: i64) -> Result<()> {
+		for (index, prerequisite) in self.prerequisites.iter().enumerate() {
+			let held = match user_badges.get(index) {
+				Some(info) => UserBadge::load_held(info, user, &prerequisite.owner, prerequisite.badge_id, now)?.is_some(),
+				None => false,
+			};
+			if !held {
+				msg!("Missing prerequisite badge {} from owner {}", prerequisite.badge_id, prerequisite.owner);
+				return err!(BadgeRewardsError::MissingPrerequisite);
+			}
+		}
+		Ok(())
+	}
+
+	/// The highest tier a holder with `points` qualifies for, 0 being the base badge
+	pub fn tier_for(&self, points: u64) -> u8 {
+		self.tiers.iter().take_while(|tier| points >= tier.required_points).count() as u8
+	}
+
+	/// Bring the yield accumulator up to `now`; must run before total_earned or emission_rate changes
+	pub fn accrue_yield(&mut self, now: i64) -> Result<()> {
+		if self.emission_rate > 0 && self.total_earned > 0 && now > self.last_yield_update {
+			let emitted = ((now - self.last_yield_update) as u128)
+				.checked_mul(self.emission_rate as u128)
+				.ok_or(BadgeRewardsError::YieldOverflow)?;
+			let accrued = emitted
+				.checked_mul(YIELD_PRECISION)
+				.ok_or(BadgeRewardsError::YieldOverflow)?
+				/ self.total_earned as u128;
+			self.acc_yield_per_badge = self.acc_yield_per_badge
+				.checked_add(accrued)
+				.ok_or(BadgeRewardsError::YieldOverflow)?;
+			self.reserved_yield = self.reserved_yield.saturating_add(u64::try_from(emitted).unwrap_or(u64::MAX));
+		}
+		self.last_yield_update = now;
+		Ok(())
+	}
+
+	/// Release a departing holder's unharvested yield from the reserve, returning it; must run
+	/// before total_earned drops. Once the last holder leaves, the accumulator's rounding is
+	/// released too.
+	pub fn release_yield(&mut self, user_badge: &UserBadge) -> u64 {
+		let pending = user_badge.pending_yield(self.acc_yield_per_badge);
+		self.reserved_yield = if self.total_earned <= 1 { 0 } else { self.reserved_yield.saturating_sub(pending) };
+		pending
+	}
+
+	/// When a badge earned or renewed at `from` expires, or None if it never does
+	pub fn expires_at(&self, from: i64) -> Option<i64> {
+		self.valid_for_seconds
+			.map(|seconds| from.saturating_add(i64::try_from(seconds).unwrap_or(i64::MAX)))
+	}
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{constants::CLOSED_BADGE_VERSION, error::BadgeRewardsError};
+
+/// Left behind when a badge is closed, so re-creating it at the same address keeps its supply count
+#[account]
+#[derive(InitSpace)]
+pub struct ClosedBadge {
+	/// Layout version, see CLOSED_BADGE_VERSION
+	pub version: u8,
+	pub owner: Pubkey,
+	pub badge_id: u64,
+	/// Badges issued over every incarnation of the badge so far
+	pub total_issued: u64,
+	pub closed_at: i64,
+	pub bump: u8,
+}
+
+impl ClosedBadge {
+	/// Read the closed badge record at `info`, if the badge has been closed before
+	pub fn load(info: &AccountInfo) -> Result<Option<ClosedBadge>> {
+		if info.data_is_empty() {
+			return Ok(None);
+		}
+		require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+		let data = info.try_borrow_data()?;
+		let closed_badge = ClosedBadge::try_deserialize(&mut &data[..])?;
+		require!(closed_badge.version == CLOSED_BADGE_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+		Ok(Some(closed_badge))
+	}
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{constants::MAX_COLLECTION_BADGES, error::BadgeRewardsError};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Collection {
+	/// Layout version, see COLLECTION_VERSION
+	pub version: u8,
+	/// Owner of the member badges and the meta-badge
+	pub owner: Pubkey,
+	pub collection_id: u64,
+	#[max_len(100)]
+	pub name: String,
+	/// Badge IDs, all from `owner`, a user must hold to complete the collection
+	#[max_len(MAX_COLLECTION_BADGES)]
+	pub badge_ids: Vec<u64>,
+	/// Badge from `owner` awarded on completion
+	pub meta_badge_id: u64,
+	pub total_completed: u64,
+	pub bump: u8,
+}
+
+impl Collection {
+	/// Exact account size for a collection holding the given name and members
+	pub fn space(name: &str, badge_ids: &[u64]) -> usize {
+		8 + Collection::INIT_SPACE - 100 + name.len()
+			- (MAX_COLLECTION_BADGES - badge_ids.len().min(MAX_COLLECTION_BADGES)) * 8
+	}
+
+	/// Validate that the members are within limits, distinct, and do not include the meta-badge
+	pub fn validate_badges(&self) -> Result<()> {
+		require!(self.badge_ids.len() <= MAX_COLLECTION_BADGES, BadgeRewardsError::TooManyCollectionBadges);
+		require!(!self.badge_ids.is_empty(), BadgeRewardsError::InvalidCollection);
+		for (index, badge_id) in self.badge_ids.iter().enumerate() {
+			require!(*badge_id != self.meta_badge_id, BadgeRewardsError::InvalidCollection);
+			require!(!self.badge_ids[..index].contains(badge_id), BadgeRewardsError::InvalidCollection);
+		}
+		Ok(())
+	}
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+	/// Layout version, see CONFIG_VERSION
+	pub version: u8,
+	pub admin: Pubkey,
+	/// While set, every mutating instruction other than set_pause, transfer_admin and set_protocol_fee fails
+	pub paused: bool,
+	/// Protocol fee in basis points
+	pub protocol_fee_bps: u16,
+	pub bump: u8,
+}
+
+impl Config {
+	/// Read the config at `info` in any layout version, with fields newer than its version zeroed
+	pub fn load_any_version(info: &AccountInfo) -> Result<Config> {
+		require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+		let mut data = info.try_borrow_data()?.to_vec();
+		data.resize(data.len() + Config::INIT_SPACE, 0);
+		Config::try_deserialize(&mut &data[..])
+	}
+}
\ No newline at end of file
@@ -1,12 +1,21 @@
-
-use anchor_lang::prelude::*;
-
 pub mod badge;
 pub mod user_badge;
 pub mod reward;
 pub mod user_reward;
+pub mod user_points;
+pub mod revoked_badge;
+pub mod config;
+pub mod collection;
+pub mod vesting_escrow;
+pub mod closed_badge;
 
 pub use badge::*;
 pub use user_badge::*;
 pub use reward::*;
 pub use user_reward::*;
+pub use user_points::*;
+pub use revoked_badge::*;
+pub use config::*;
+pub use collection::*;
+pub use vesting_escrow::*;
+pub use closed_badge::*;
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::{constants::REVOKED_BADGE_VERSION, error::BadgeRewardsError};
+
+#[account]
+#[derive(InitSpace)]
+pub struct RevokedBadge {
+	/// Layout version, see REVOKED_BADGE_VERSION
+	pub version: u8,
+	pub user_badge: Pubkey,
+	pub user: Pubkey,
+	pub badge_owner: Pubkey,
+	pub badge_id: u64,
+	pub revoked_by: Pubkey,
+	pub revoked_at: i64,
+	pub reason_code: u8,
+	#[max_len(200)]
+	pub evidence_uri: Option<String>,
+	pub allow_reissue: bool,
+	pub reissued_at: Option<i64>,
+	pub bump: u8,
+}
+
+impl RevokedBadge {
+	/// Read the revocation record at `info`, if one has been created
+	pub fn load(info: &AccountInfo) -> Result<Option<RevokedBadge>> {
+		if info.data_is_empty() {
+			return Ok(None);
+		}
+		require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+		let data = info.try_borrow_data()?;
+		let revoked_badge = RevokedBadge::try_deserialize(&mut &data[..])?;
+		require!(revoked_badge.version == REVOKED_BADGE_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+		Ok(Some(revoked_badge))
+	}
+}
\ No newline at end of file
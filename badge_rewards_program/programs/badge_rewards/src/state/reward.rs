@@ -1,16 +1,97 @@
 use anchor_lang::prelude::*;
+use crate::error::BadgeRewardsError;
 
 #[account]
+#[derive(InitSpace)]
 pub struct Reward {
+	/// Layout version, see REWARD_VERSION
+	pub version: u8,
+	/// Issuer that created the reward; part of the PDA seeds and never changes
 	pub owner: Pubkey,
+	/// Wallet currently allowed to manage the reward
+	pub authority: Pubkey,
+	/// Wallet proposed as the next authority, pending its acceptance
+	pub pending_authority: Option<Pubkey>,
 	pub badge_id: u64,
 	pub reward_id: u64,
+	#[max_len(100)]
 	pub name: String,
+	#[max_len(500)]
 	pub description: String,
 	pub reward_type: u8,
 	pub reward_value: u64,
 	pub token_mint: Option<Pubkey>,
 	pub nft_mint: Option<Pubkey>,
 	pub is_active: bool,
+	pub stake_status: StakeStatus,
+	pub staked_by: Pubkey,
+	pub staked_at: i64,
+	pub min_stake_period: u64,
+	pub can_unstake_at: i64,
 	pub bump: u8,
+	/// Lowest badge tier that may claim this reward, 0 being the base badge
+	pub min_tier: u8,
+	/// How many users may claim this reward, or None if unlimited
+	pub max_claims: Option<u64>,
+	pub total_claimed: u64,
+	/// Claims are rejected before this time, if set
+	pub claim_start: Option<i64>,
+	/// Claims are rejected from this time on, after which the vault can be swept, if set
+	pub claim_end: Option<i64>,
+	/// If set, token claims are paid into a per-user vesting escrow instead of directly
+	pub vesting: Option<VestingSchedule>,
+	/// Latest end of any vesting escrow claimed from this reward; the vault stays open until then
+	pub vesting_ends_at: i64,
+}
+
+impl Reward {
+	/// Exact account size for a reward holding the given strings
+	pub fn space(name: &str, description: &str) -> usize {
+		8 + Reward::INIT_SPACE - (100 + 500) + name.len() + description.len()
+	}
+
+	/// Validate that the claim window, if bounded on both sides, opens before it closes
+	pub fn validate_claim_window(&self) -> Result<()> {
+		if let (Some(claim_start), Some(claim_end)) = (self.claim_start, self.claim_end) {
+			require!(claim_start < claim_end, BadgeRewardsError::InvalidClaimWindow);
+		}
+		Ok(())
+	}
+
+	/// Validate that a vesting schedule is only set on token rewards and has its cliff within its duration
+	pub fn validate_vesting(&self) -> Result<()> {
+		if let Some(vesting) = self.vesting {
+			require!(self.reward_type == 0, BadgeRewardsError::InvalidVestingSchedule);
+			require!(vesting.duration_seconds > 0, BadgeRewardsError::InvalidVestingSchedule);
+			require!(vesting.cliff_seconds <= vesting.duration_seconds, BadgeRewardsError::InvalidVestingSchedule);
+		}
+		Ok(())
+	}
+}
+
+/// Linear unlock of a claimed token reward, measured from the claim
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VestingSchedule {
+	/// Nothing unlocks until this long after the claim
+	pub cliff_seconds: u64,
+	/// Everything is unlocked this long after the claim
+	pub duration_seconds: u64,
+}
+
+impl VestingSchedule {
+	/// The (cliff_at, end_at) times for a claim made at `start`
+	pub fn times_from(&self, start: i64) -> (i64, i64) {
+		let offset = |seconds: u64| start.saturating_add(i64::try_from(seconds).unwrap_or(i64::MAX));
+		(offset(self.cliff_seconds), offset(self.duration_seconds))
+	}
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StakeStatus {
+	/// Reward is available for claiming/use
+	Available,
+	/// Reward is staked by the business owner and cannot be claimed
+	StakedByBusiness,
+	/// Reward is staked by the player who claimed it
+	StakedByPlayer,
 }
\ No newline at end of file
@@ -1,9 +1,63 @@
 use anchor_lang::prelude::*;
+use crate::{constants::{USER_BADGE_VERSION, YIELD_PRECISION}, error::BadgeRewardsError};
 
 #[account]
+#[derive(InitSpace)]
 pub struct UserBadge {
+	/// Layout version, see USER_BADGE_VERSION
+	pub version: u8,
 	pub user: Pubkey,
 	pub badge_id: u64,
 	pub earned_at: i64,
+	pub rent_payer: Pubkey,
+	/// Soulbound Token-2022 NFT minted for this badge, if the badge mints one
+	pub nft_mint: Option<Pubkey>,
 	pub bump: u8,
+	/// When the badge stops being valid, or None if it never expires
+	pub expires_at: Option<i64>,
+	/// Current tier, 0 being the base badge and N being `Badge.tiers[N - 1]`
+	pub tier: u8,
+	/// The Badge account this was issued from, since the PDA seeds do not include the badge owner
+	pub badge: Pubkey,
+	/// The badge's acc_yield_per_badge when this badge's yield was last settled
+	pub yield_debt: u128,
+}
+
+impl UserBadge {
+	/// Read the user_badge at `info`, if one has been created
+	pub fn load(info: &AccountInfo) -> Result<Option<UserBadge>> {
+		if info.data_is_empty() {
+			return Ok(None);
+		}
+		require_keys_eq!(*info.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+		let data = info.try_borrow_data()?;
+		let user_badge = UserBadge::try_deserialize(&mut &data[..])?;
+		require!(user_badge.version == USER_BADGE_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+		Ok(Some(user_badge))
+	}
+
+	/// Read the user_badge at `info` if it shows `user` holding `owner`'s badge `badge_id` unexpired at `now`
+	pub fn load_held(info: &AccountInfo, user: &Pubkey, owner: &Pubkey, badge_id: u64, now: i64) -> Result<Option<UserBadge>> {
+		if info.data_is_empty() || *info.owner != crate::ID {
+			return Ok(None);
+		}
+		let data = info.try_borrow_data()?;
+		let Ok(user_badge) = UserBadge::try_deserialize(&mut &data[..]) else {
+			return Ok(None);
+		};
+		require!(user_badge.version == USER_BADGE_VERSION, BadgeRewardsError::UnsupportedAccountVersion);
+		
+		let (badge, _) = Pubkey::find_program_address(&[b"badge", owner.as_ref(), &badge_id.to_le_bytes()], &crate::ID);
+		let held = user_badge.user == *user
+			&& user_badge.badge_id == badge_id
+			&& user_badge.badge == badge
+			&& !matches!(user_badge.expires_at, Some(expires_at) if now >= expires_at);
+		Ok(held.then_some(user_badge))
+	}
+
+	/// Yield accrued since the last harvest, given the badge's current accumulator
+	pub fn pending_yield(&self, acc_yield_per_badge: u128) -> u64 {
+		let pending = acc_yield_per_badge.saturating_sub(self.yield_debt) / YIELD_PRECISION;
+		u64::try_from(pending).unwrap_or(u64::MAX)
+	}
 }
\ No newline at end of file
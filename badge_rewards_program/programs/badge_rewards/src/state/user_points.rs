@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserPoints {
+	/// Layout version, see USER_POINTS_VERSION
+	pub version: u8,
+	pub owner: Pubkey,
+	pub user: Pubkey,
+	pub points: u64,
+	pub bump: u8,
+}
\ No newline at end of file
@@ -1,9 +1,23 @@
 use anchor_lang::prelude::*;
+use crate::state::StakeStatus;
 
 #[account]
+#[derive(InitSpace)]
 pub struct UserReward {
+	/// Layout version, see USER_REWARD_VERSION
+	pub version: u8,
 	pub user: Pubkey,
 	pub reward_id: u64,
 	pub claimed_at: i64,
+	pub claimed_amount: u64,
+	pub stake_status: StakeStatus,
+	pub staked_at: i64,
+	pub min_stake_period: u64,
+	pub can_unstake_at: i64,
+	pub locked_amount: u64,
 	pub bump: u8,
+	/// The Reward account this was claimed from, since the PDA seeds do not include the reward owner
+	pub reward: Pubkey,
+	/// Issuer of the reward, kept so events can name it after the reward is gone
+	pub owner: Pubkey,
 }
\ No newline at end of file
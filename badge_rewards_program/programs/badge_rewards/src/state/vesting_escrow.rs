@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::RevokedBadge;
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingEscrow {
+	/// Layout version, see VESTING_ESCROW_VERSION
+	pub version: u8,
+	pub user: Pubkey,
+	pub reward: Pubkey,
+	/// The Badge the reward was claimed with; revoking it from the user stops vesting
+	pub badge: Pubkey,
+	pub mint: Pubkey,
+	pub total_amount: u64,
+	pub withdrawn_amount: u64,
+	pub start_at: i64,
+	/// Nothing unlocks before this time
+	pub cliff_at: i64,
+	/// Everything is unlocked from this time on
+	pub end_at: i64,
+	pub bump: u8,
+}
+
+impl VestingEscrow {
+	/// Amount unlocked at `at`: nothing before the cliff, then linearly from start_at to end_at
+	pub fn vested_amount(&self, at: i64) -> u64 {
+		if at >= self.end_at {
+			return self.total_amount;
+		}
+		if at < self.cliff_at || at <= self.start_at {
+			return 0;
+		}
+		let elapsed = (at - self.start_at) as u128;
+		let duration = (self.end_at - self.start_at) as u128;
+		(self.total_amount as u128 * elapsed / duration) as u64
+	}
+
+	/// When vesting stops: `now`, or the revocation time if the badge was revoked after the claim
+	pub fn vesting_stopped_at(&self, revoked_badge: Option<&RevokedBadge>, now: i64) -> i64 {
+		match revoked_badge {
+			Some(revoked_badge) if revoked_badge.revoked_at >= self.start_at => now.min(revoked_badge.revoked_at),
+			_ => now,
+		}
+	}
+}
\ No newline at end of file
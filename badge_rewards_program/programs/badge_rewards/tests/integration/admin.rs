@@ -0,0 +1,100 @@
+use crate::common::*;
+use ::badge_rewards::{Badge, BadgeRewardsError, Config, Reward};
+use anchor_lang::prelude::Pubkey;
+use solana_program_test::tokio;
+use solana_sdk::signer::Signer as _;
+
+#[tokio::test]
+async fn pause_blocks_instructions_until_unpaused() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let admin = env.admin.insecure_clone();
+
+    let pause = env.set_pause(&owner.pubkey(), true);
+    assert_error(env.send(&[pause], &[&owner]).await, BadgeRewardsError::Unauthorized);
+    let pause = env.set_pause(&admin.pubkey(), true);
+    env.send(&[pause], &[&admin]).await.unwrap();
+    let config: Config = env.state(&config_pda()).await;
+    assert!(config.paused);
+
+    let create = env.initialize_badge(&owner.pubkey(), badge_args(1));
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::ProgramPaused);
+    let unpause = env.set_pause(&admin.pubkey(), false);
+    env.send(&[unpause], &[&admin]).await.unwrap();
+    env.create_badge(&owner, badge_args(1)).await;
+}
+
+#[tokio::test]
+async fn admin_can_be_transferred_and_set_the_protocol_fee() {
+    let mut env = Env::new().await;
+    let admin = env.admin.insecure_clone();
+    let new_admin = env.keypair().await;
+
+    let fee = env.set_protocol_fee(&admin.pubkey(), 10_001);
+    assert_error(env.send(&[fee], &[&admin]).await, BadgeRewardsError::InvalidProtocolFee);
+    let fee = env.set_protocol_fee(&admin.pubkey(), 250);
+    env.send(&[fee], &[&admin]).await.unwrap();
+    let config: Config = env.state(&config_pda()).await;
+    assert_eq!(config.protocol_fee_bps, 250);
+
+    let transfer = env.transfer_admin(&admin.pubkey(), &Pubkey::default());
+    assert_error(env.send(&[transfer], &[&admin]).await, BadgeRewardsError::InvalidAdmin);
+    let transfer = env.transfer_admin(&admin.pubkey(), &new_admin.pubkey());
+    env.send(&[transfer], &[&admin]).await.unwrap();
+    let config: Config = env.state(&config_pda()).await;
+    assert_eq!(config.admin, new_admin.pubkey());
+
+    let fee = env.set_protocol_fee(&admin.pubkey(), 0);
+    assert_error(env.send(&[fee], &[&admin]).await, BadgeRewardsError::Unauthorized);
+    let fee = env.set_protocol_fee(&new_admin.pubkey(), 0);
+    env.send(&[fee], &[&new_admin]).await.unwrap();
+}
+
+#[tokio::test]
+async fn badge_authority_moves_only_when_the_proposed_owner_accepts() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let successor = env.keypair().await;
+    let stranger = env.keypair().await;
+    env.create_badge(&owner, badge_args(1)).await;
+
+    let propose = env.propose_badge_owner(&owner.pubkey(), 1, &stranger.pubkey(), Some(stranger.pubkey()));
+    assert_error(env.send(&[propose], &[&stranger]).await, BadgeRewardsError::Unauthorized);
+    let propose = env.propose_badge_owner(&owner.pubkey(), 1, &owner.pubkey(), Some(successor.pubkey()));
+    env.send(&[propose], &[&owner]).await.unwrap();
+    let accept = env.accept_badge_owner(&owner.pubkey(), 1, &stranger.pubkey());
+    assert_error(env.send(&[accept], &[&stranger]).await, BadgeRewardsError::NotPendingOwner);
+    let accept = env.accept_badge_owner(&owner.pubkey(), 1, &successor.pubkey());
+    env.send(&[accept], &[&successor]).await.unwrap();
+
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(badge.owner, owner.pubkey());
+    assert_eq!(badge.authority, successor.pubkey());
+    assert_eq!(badge.pending_authority, None);
+    let propose = env.propose_badge_owner(&owner.pubkey(), 1, &owner.pubkey(), None);
+    assert_error(env.send(&[propose], &[&owner]).await, BadgeRewardsError::Unauthorized);
+}
+
+#[tokio::test]
+async fn reward_authority_moves_only_when_the_proposed_owner_accepts() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let successor = env.keypair().await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.create_reward(&owner, reward_args(1, 1), None).await;
+
+    let propose = env.propose_reward_owner(&owner.pubkey(), 1, 1, &owner.pubkey(), Some(successor.pubkey()));
+    env.send(&[propose], &[&owner]).await.unwrap();
+    let cancel = env.propose_reward_owner(&owner.pubkey(), 1, 1, &owner.pubkey(), None);
+    env.send(&[cancel], &[&owner]).await.unwrap();
+    let accept = env.accept_reward_owner(&owner.pubkey(), 1, 1, &successor.pubkey());
+    assert_error(env.send(&[accept], &[&successor]).await, BadgeRewardsError::NotPendingOwner);
+
+    let propose = env.propose_reward_owner(&owner.pubkey(), 1, 1, &owner.pubkey(), Some(successor.pubkey()));
+    env.send(&[propose], &[&owner]).await.unwrap();
+    let accept = env.accept_reward_owner(&owner.pubkey(), 1, 1, &successor.pubkey());
+    env.send(&[accept], &[&successor]).await.unwrap();
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert_eq!(reward.authority, successor.pubkey());
+    assert_eq!(reward.pending_authority, None);
+}
\ No newline at end of file
@@ -0,0 +1,375 @@
+use crate::common::*;
+use ::badge_rewards::{instruction, BADGE_CLOSE_GRACE_PERIOD, Badge, BadgePrerequisite, BadgeRewardsError, BadgeTier, RevokedBadge, UserBadge, UserPoints};
+use anchor_lang::error::ErrorCode;
+use solana_program_test::tokio;
+use solana_sdk::{signature::Keypair, signer::Signer as _};
+
+#[tokio::test]
+async fn initialize_badge_sets_owner_and_limits() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let badge = env.create_badge(&owner, badge_args(1)).await;
+
+    let badge: Badge = env.state(&badge).await;
+    assert_eq!(badge.owner, owner.pubkey());
+    assert_eq!(badge.authority, owner.pubkey());
+    assert_eq!(badge.required_points, 10);
+    assert_eq!(badge.max_earnings, 100);
+    assert_eq!(badge.total_issued, 0);
+    assert!(badge.is_active);
+
+    let instruction = env.initialize_badge(&owner.pubkey(), instruction::InitializeBadge { required_points: 0, ..badge_args(2) });
+    assert_error(env.send(&[instruction], &[&owner]).await, BadgeRewardsError::InvalidRequiredPoints);
+    let instruction = env.initialize_badge(&owner.pubkey(), instruction::InitializeBadge { valid_for_seconds: Some(0), ..badge_args(2) });
+    assert_error(env.send(&[instruction], &[&owner]).await, BadgeRewardsError::InvalidValidityPeriod);
+}
+
+#[tokio::test]
+async fn points_are_awarded_and_deducted_by_the_owner() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 25);
+    env.send(&[award], &[&owner]).await.unwrap();
+    let deduct = env.deduct_points(&owner.pubkey(), &user.pubkey(), 5);
+    env.send(&[deduct], &[&owner]).await.unwrap();
+    let points: UserPoints = env.state(&user_points_pda(&owner.pubkey(), &user.pubkey())).await;
+    assert_eq!(points.owner, owner.pubkey());
+    assert_eq!(points.user, user.pubkey());
+    assert_eq!(points.points, 20);
+
+    let deduct = env.deduct_points(&owner.pubkey(), &user.pubkey(), 21);
+    assert_error(env.send(&[deduct], &[&owner]).await, BadgeRewardsError::InsufficientPoints);
+
+    // Handing a badge to another authority does not let it mint the owner's points
+    let successor = env.keypair().await;
+    let propose = env.propose_badge_owner(&owner.pubkey(), 1, &owner.pubkey(), Some(successor.pubkey()));
+    let accept = env.accept_badge_owner(&owner.pubkey(), 1, &successor.pubkey());
+    env.send(&[propose, accept], &[&owner, &successor]).await.unwrap();
+    let award = replace_account(env.award_points(&owner.pubkey(), &user.pubkey(), 5), &owner.pubkey(), &successor.pubkey());
+    assert_error(env.send(&[award], &[&successor]).await, ErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn stake_badge_issues_badge_to_user_with_points() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let badge = env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.user, user.pubkey());
+    assert_eq!(user_badge.badge, badge);
+    assert_eq!(user_badge.expires_at, None);
+    let badge: Badge = env.state(&badge).await;
+    assert_eq!(badge.total_issued, 1);
+    assert_eq!(badge.total_earned, 1);
+}
+
+#[tokio::test]
+async fn stake_badge_requires_enough_points() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 9);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &user.pubkey(), false);
+    assert_error(env.send(&[award, stake], &[&owner, &user]).await, BadgeRewardsError::InsufficientPoints);
+}
+
+#[tokio::test]
+async fn stake_badge_stops_at_max_earnings() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    env.create_badge(&owner, instruction::InitializeBadge { max_earnings: 1, ..badge_args(1) }).await;
+    env.earn_badge(&owner, 1, &Keypair::new()).await;
+
+    let user = Keypair::new();
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 10);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &user.pubkey(), false);
+    assert_error(env.send(&[award, stake], &[&owner, &user]).await, BadgeRewardsError::MaxEarningsReached);
+}
+
+#[tokio::test]
+async fn stake_badge_requires_prerequisites_in_order() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.create_badge(&owner, instruction::InitializeBadge {
+        prerequisites: vec![BadgePrerequisite { owner: owner.pubkey(), badge_id: 1 }],
+        ..badge_args(2)
+    }).await;
+
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 10);
+    let stake = env.stake_badge(&owner.pubkey(), 2, &user.pubkey(), false);
+    assert_error(env.send(&[award, stake], &[&owner, &user]).await, BadgeRewardsError::MissingPrerequisite);
+
+    env.earn_badge(&owner, 1, &user).await;
+    let stake = with_remaining(env.stake_badge(&owner.pubkey(), 2, &user.pubkey(), false), &[user_badge_pda(&user.pubkey(), 1)]);
+    env.send(&[stake], &[&owner, &user]).await.unwrap();
+    assert!(env.exists(&user_badge_pda(&user.pubkey(), 2)).await);
+}
+
+#[tokio::test]
+async fn stake_badge_mints_soulbound_nft_that_unstake_burns() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let badge = env.create_badge(&owner, instruction::InitializeBadge { mint_nft: true, ..badge_args(1) }).await;
+
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 10);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &user.pubkey(), true);
+    env.send(&[award, stake], &[&owner, &user]).await.unwrap();
+    let badge_mint = badge_mint_pda(&badge, &user.pubkey());
+    let nft_account = token_account(&user.pubkey(), &badge_mint);
+    assert_eq!(env.balance(&nft_account).await, 1);
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.nft_mint, Some(badge_mint));
+
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &user.pubkey(), false, None);
+    assert_error(env.send(&[unstake], &[&owner, &user]).await, BadgeRewardsError::MissingBadgeMint);
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &user.pubkey(), true, None);
+    env.send(&[unstake], &[&owner, &user]).await.unwrap();
+    assert_eq!(env.balance(&nft_account).await, 0);
+}
+
+#[tokio::test]
+async fn unstake_badge_closes_user_badge_and_keeps_lifetime_count() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let badge = env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    let stranger = Keypair::new();
+    let unstake = replace_account(env.unstake_badge(&owner.pubkey(), 1, &user.pubkey(), false, None), &owner.pubkey(), &stranger.pubkey());
+    assert_error(env.send(&[unstake], &[&stranger, &user]).await, BadgeRewardsError::Unauthorized);
+
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &user.pubkey(), false, None);
+    env.send(&[unstake], &[&owner, &user]).await.unwrap();
+    assert!(!env.exists(&user_badge_pda(&user.pubkey(), 1)).await);
+    let badge: Badge = env.state(&badge).await;
+    assert_eq!(badge.total_earned, 0);
+    assert_eq!(badge.total_issued, 1);
+}
+
+#[tokio::test]
+async fn revoked_badge_is_only_reissued_when_allowed() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let badge = env.create_badge(&owner, badge_args(1)).await;
+    env.create_badge(&owner, badge_args(2)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.earn_badge(&owner, 2, &user).await;
+
+    let revoke = env.revoke_badge(&owner.pubkey(), 1, &user.pubkey(), false, false);
+    env.send(&[revoke], &[&owner]).await.unwrap();
+    let revoked_badge: RevokedBadge = env.state(&revoked_badge_pda(&badge, &user.pubkey())).await;
+    assert_eq!(revoked_badge.user, user.pubkey());
+    assert!(!revoked_badge.allow_reissue);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &user.pubkey(), false);
+    assert_error(env.send(&[stake], &[&owner, &user]).await, BadgeRewardsError::BadgeRevoked);
+
+    let revoke = env.revoke_badge(&owner.pubkey(), 2, &user.pubkey(), true, false);
+    env.send(&[revoke], &[&owner]).await.unwrap();
+    let stake = env.stake_badge(&owner.pubkey(), 2, &user.pubkey(), false);
+    env.send(&[stake], &[&owner, &user]).await.unwrap();
+    let revoked_badge: RevokedBadge = env.state(&revoked_badge_pda(&badge_pda(&owner.pubkey(), 2), &user.pubkey())).await;
+    assert!(revoked_badge.reissued_at.is_some());
+}
+
+#[tokio::test]
+async fn expiring_badge_can_be_renewed() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, instruction::InitializeBadge { valid_for_seconds: Some(100), ..badge_args(1) }).await;
+    env.create_badge(&owner, badge_args(2)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.earn_badge(&owner, 2, &user).await;
+    let earned_at = env.now().await;
+
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.expires_at, Some(earned_at + 100));
+
+    env.warp(40).await;
+    let renew = env.renew_badge(&owner.pubkey(), 1, &user.pubkey());
+    env.send(&[renew], &[&owner]).await.unwrap();
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.expires_at, Some(earned_at + 200));
+
+    let renew = env.renew_badge(&owner.pubkey(), 2, &user.pubkey());
+    assert_error(env.send(&[renew], &[&owner]).await, BadgeRewardsError::BadgeDoesNotExpire);
+}
+
+#[tokio::test]
+async fn upgrade_badge_moves_to_the_highest_reached_tier() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let tier = |required_points| BadgeTier { required_points, icon_uri: "https://example.com/tier.png".to_string() };
+    env.create_badge(&owner, instruction::InitializeBadge { tiers: vec![tier(20), tier(30)], ..badge_args(1) }).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    let upgrade = env.upgrade_badge(&owner.pubkey(), 1, &user.pubkey());
+    assert_error(env.send(&[upgrade], &[&user]).await, BadgeRewardsError::NoTierUpgrade);
+
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 25);
+    let upgrade = env.upgrade_badge(&owner.pubkey(), 1, &user.pubkey());
+    env.send(&[award, upgrade], &[&owner, &user]).await.unwrap();
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.tier, 2);
+}
+
+#[tokio::test]
+async fn update_badge_keeps_limits_consistent() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    let update = || instruction::UpdateBadge {
+        badge_id: 1,
+        name: None,
+        description: None,
+        icon_uri: None,
+        required_points: None,
+        max_earnings: None,
+        is_active: None,
+        tiers: None,
+        prerequisites: None,
+    };
+
+    let instruction = env.update_badge(&owner.pubkey(), instruction::UpdateBadge { max_earnings: Some(0), ..update() });
+    assert_error(env.send(&[instruction], &[&owner]).await, BadgeRewardsError::InvalidMaxEarnings);
+    let instruction = env.update_badge(&owner.pubkey(), instruction::UpdateBadge {
+        tiers: Some(vec![BadgeTier { required_points: 10, icon_uri: String::new() }]),
+        ..update()
+    });
+    assert_error(env.send(&[instruction], &[&owner]).await, BadgeRewardsError::InvalidTierPoints);
+
+    let instruction = env.update_badge(&owner.pubkey(), instruction::UpdateBadge { max_earnings: Some(1), required_points: Some(5), ..update() });
+    env.send(&[instruction], &[&owner]).await.unwrap();
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(badge.max_earnings, 1);
+    assert_eq!(badge.required_points, 5);
+    let other = Keypair::new();
+    let award = env.award_points(&owner.pubkey(), &other.pubkey(), 5);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &other.pubkey(), false);
+    assert_error(env.send(&[award, stake], &[&owner, &other]).await, BadgeRewardsError::MaxEarningsReached);
+}
+
+#[tokio::test]
+async fn frozen_badge_cannot_be_earned() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+
+    let freeze = env.freeze_badge(&owner.pubkey(), 1);
+    env.send(&[freeze], &[&owner]).await.unwrap();
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 10);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &user.pubkey(), false);
+    assert_error(env.send(&[award, stake], &[&owner, &user]).await, BadgeRewardsError::BadgeNotActive);
+}
+
+#[tokio::test]
+async fn close_badge_returns_rent_once_no_one_holds_it() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let badge = env.create_badge(&owner, badge_args(1)).await;
+    env.create_badge(&owner, badge_args(2)).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    let close = env.close_badge(&owner.pubkey(), 1, None);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::BadgeHasHolders);
+
+    let rent = env.lamports(&badge_pda(&owner.pubkey(), 2)).await;
+    let balance = env.lamports(&owner.pubkey()).await;
+    let close = env.close_badge(&owner.pubkey(), 2, None);
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert!(!env.exists(&badge_pda(&owner.pubkey(), 2)).await);
+    assert_eq!(env.lamports(&owner.pubkey()).await, balance + rent);
+    assert!(env.exists(&badge).await);
+}
+
+#[tokio::test]
+async fn closed_badge_keeps_its_supply_when_recreated() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let badge = env.create_badge(&owner, instruction::InitializeBadge { max_earnings: 1, ..badge_args(1) }).await;
+    env.earn_badge(&owner, 1, &user).await;
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &user.pubkey(), false, None);
+    env.send(&[unstake], &[&owner, &user]).await.unwrap();
+
+    let close = env.close_badge(&owner.pubkey(), 1, None);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::BadgeCloseGracePeriod);
+    let freeze = env.freeze_badge(&owner.pubkey(), 1);
+    env.send(&[freeze], &[&owner]).await.unwrap();
+    env.warp(BADGE_CLOSE_GRACE_PERIOD - 1).await;
+    let close = env.close_badge(&owner.pubkey(), 1, None);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::BadgeCloseGracePeriod);
+    env.warp(1).await;
+    let close = env.close_badge(&owner.pubkey(), 1, None);
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert!(!env.exists(&badge).await);
+
+    env.create_badge(&owner, instruction::InitializeBadge { max_earnings: 1, ..badge_args(1) }).await;
+    let recreated: Badge = env.state(&badge).await;
+    assert_eq!(recreated.total_issued, 1);
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 10);
+    let stake = env.stake_badge(&owner.pubkey(), 1, &user.pubkey(), false);
+    assert_error(env.send(&[award, stake], &[&owner, &user]).await, BadgeRewardsError::MaxEarningsReached);
+}
+
+#[tokio::test]
+async fn backfill_binds_a_migrated_user_badge_only_to_its_own_badge() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let rival = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    let nft_badge = env.create_badge(&owner, instruction::InitializeBadge { mint_nft: true, ..badge_args(2) }).await;
+    env.create_badge(&rival, badge_args(1)).await;
+    env.create_badge(&rival, badge_args(2)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    let stake = env.stake_badge(&owner.pubkey(), 2, &user.pubkey(), true);
+    env.send(&[stake], &[&owner, &user]).await.unwrap();
+
+    // Written before user badges recorded their badge
+    for badge_id in [1, 2] {
+        let key = user_badge_pda(&user.pubkey(), badge_id);
+        let mut user_badge: UserBadge = env.state(&key).await;
+        user_badge.badge = Default::default();
+        env.set_state(&key, &user_badge).await;
+    }
+
+    // Another owner's badge with the same id cannot claim them
+    let backfill = env.backfill_user_badge(&rival.pubkey(), 1, &rival.pubkey(), &user.pubkey(), false);
+    assert_error(env.send(&[backfill], &[&rival]).await, BadgeRewardsError::Unauthorized);
+    let backfill = env.backfill_user_badge(&rival.pubkey(), 2, &rival.pubkey(), &user.pubkey(), true);
+    assert_error(env.send(&[backfill], &[&rival, &user]).await, BadgeRewardsError::UserBadgeMismatch);
+
+    // The soulbound NFT proves the badge; without one the user co-signs
+    let backfill = env.backfill_user_badge(&owner.pubkey(), 2, &owner.pubkey(), &user.pubkey(), false);
+    env.send(&[backfill], &[&owner]).await.unwrap();
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 2)).await;
+    assert_eq!(user_badge.badge, nft_badge);
+    let backfill = env.backfill_user_badge(&owner.pubkey(), 1, &owner.pubkey(), &user.pubkey(), false);
+    assert_error(env.send(&[backfill], &[&owner]).await, BadgeRewardsError::Unauthorized);
+    let backfill = env.backfill_user_badge(&owner.pubkey(), 1, &owner.pubkey(), &user.pubkey(), true);
+    env.send(&[backfill], &[&owner, &user]).await.unwrap();
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.badge, badge_pda(&owner.pubkey(), 1));
+
+    let backfill = env.backfill_user_badge(&owner.pubkey(), 1, &owner.pubkey(), &user.pubkey(), true);
+    assert_error(env.send(&[backfill], &[&owner, &user]).await, BadgeRewardsError::UserBadgeMismatch);
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use crate::common::*;
+use ::badge_rewards::{instruction, Badge, BadgePrerequisite, BadgeRewardsError, Collection, UserBadge};
+use solana_program_test::tokio;
+use solana_sdk::{signature::Keypair, signer::Signer as _};
+
+#[tokio::test]
+async fn complete_collection_awards_meta_badge_to_holders_of_every_member() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.create_badge(&owner, badge_args(2)).await;
+    let meta_badge = env.create_badge(&owner, badge_args(3)).await;
+    let create = env.initialize_collection(&owner.pubkey(), 1, vec![1, 2], 3);
+    env.send(&[create], &[&owner]).await.unwrap();
+    env.earn_badge(&owner, 1, &user).await;
+
+    let complete = env.complete_collection(&owner.pubkey(), 1, 3, &user.pubkey(), &[1]);
+    assert_error(env.send(&[complete], &[&user]).await, BadgeRewardsError::CollectionIncomplete);
+    let complete = env.complete_collection(&owner.pubkey(), 1, 3, &user.pubkey(), &[1, 2]);
+    assert_error(env.send(&[complete], &[&user]).await, BadgeRewardsError::CollectionIncomplete);
+
+    env.earn_badge(&owner, 2, &user).await;
+    let complete = env.complete_collection(&owner.pubkey(), 1, 3, &user.pubkey(), &[2, 1]);
+    assert_error(env.send(&[complete], &[&user]).await, BadgeRewardsError::CollectionIncomplete);
+    let complete = env.complete_collection(&owner.pubkey(), 1, 3, &user.pubkey(), &[1, 2]);
+    env.send(&[complete], &[&user]).await.unwrap();
+
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 3)).await;
+    assert_eq!(user_badge.badge, meta_badge);
+    assert_eq!(user_badge.user, user.pubkey());
+    let meta_badge: Badge = env.state(&meta_badge).await;
+    assert_eq!(meta_badge.total_issued, 1);
+    assert_eq!(meta_badge.total_earned, 1);
+    let collection: Collection = env.state(&collection_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(collection.total_completed, 1);
+}
+
+#[tokio::test]
+async fn complete_collection_requires_the_meta_badge_points_and_prerequisites() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.create_badge(&owner, badge_args(2)).await;
+    env.create_badge(&owner, badge_args(3)).await;
+    env.create_badge(&owner, instruction::InitializeBadge {
+        required_points: 50,
+        prerequisites: vec![BadgePrerequisite { owner: owner.pubkey(), badge_id: 3 }],
+        ..badge_args(4)
+    }).await;
+    let create = env.initialize_collection(&owner.pubkey(), 1, vec![1, 2], 4);
+    env.send(&[create], &[&owner]).await.unwrap();
+    env.earn_badge(&owner, 1, &user).await;
+    env.earn_badge(&owner, 2, &user).await;
+
+    let complete = env.complete_collection(&owner.pubkey(), 1, 4, &user.pubkey(), &[1, 2]);
+    assert_error(env.send(&[complete], &[&user]).await, BadgeRewardsError::InsufficientPoints);
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 30);
+    env.send(&[award], &[&owner]).await.unwrap();
+    let complete = env.complete_collection(&owner.pubkey(), 1, 4, &user.pubkey(), &[1, 2]);
+    assert_error(env.send(&[complete], &[&user]).await, BadgeRewardsError::MissingPrerequisite);
+
+    env.earn_badge(&owner, 3, &user).await;
+    let complete = env.complete_collection(&owner.pubkey(), 1, 4, &user.pubkey(), &[1, 2]);
+    assert_error(env.send(&[complete], &[&user]).await, BadgeRewardsError::MissingPrerequisite);
+    let complete = with_remaining(env.complete_collection(&owner.pubkey(), 1, 4, &user.pubkey(), &[1, 2]), &[user_badge_pda(&user.pubkey(), 3)]);
+    env.send(&[complete], &[&user]).await.unwrap();
+    assert!(env.exists(&user_badge_pda(&user.pubkey(), 4)).await);
+}
+
+#[tokio::test]
+async fn initialize_collection_validates_its_badges() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.create_badge(&owner, instruction::InitializeBadge { mint_nft: true, ..badge_args(2) }).await;
+
+    let create = env.initialize_collection(&owner.pubkey(), 1, vec![2, 1], 1);
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::InvalidCollection);
+    let create = env.initialize_collection(&owner.pubkey(), 1, vec![2, 2], 1);
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::InvalidCollection);
+    let create = env.initialize_collection(&owner.pubkey(), 1, vec![], 1);
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::InvalidCollection);
+    let create = env.initialize_collection(&owner.pubkey(), 1, vec![1], 2);
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::MetaBadgeMintsNft);
+
+    let stranger = env.keypair().await;
+    let create = replace_account(env.initialize_collection(&owner.pubkey(), 1, vec![2], 1), &owner.pubkey(), &stranger.pubkey());
+    assert_error(env.send(&[create], &[&stranger]).await, BadgeRewardsError::Unauthorized);
+}
\ No newline at end of file
@@ -0,0 +1,919 @@
+//! Shared fixtures for the program tests: a bank with the config already seeded, PDA
+//! helpers, Token-2022 helpers and one instruction builder per program instruction
+
+use ::badge_rewards::{accounts, instruction, Config, CONFIG_VERSION};
+use anchor_lang::{
+    prelude::*,
+    solana_program::instruction::Instruction,
+    system_program,
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account},
+    token_2022::spl_token_2022::{
+        self,
+        extension::{transfer_fee, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    },
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account as SdkAccount,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::InstructionError,
+    signature::Keypair,
+    signer::Signer as _,
+    transaction::{Transaction, TransactionError},
+};
+use solana_system_interface::instruction as system_instruction;
+
+pub const TOKEN_PROGRAM: Pubkey = spl_token_2022::ID;
+
+/// Fill in the event_authority and program accounts every `#[event_cpi]` instruction takes
+macro_rules! event_accounts {
+    ($name:ident { $($field:ident $(: $value:expr)?),* $(,)? }) => {
+        accounts::$name {
+            $($field $(: $value)?,)*
+            event_authority: event_authority(),
+            program: ::badge_rewards::ID,
+        }
+    };
+}
+
+fn process(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    // The entrypoint ties the account slice to the account infos' lifetime
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    ::badge_rewards::entry(program_id, accounts, data)
+}
+
+fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ::badge_rewards::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Append remaining accounts, read-only, to an instruction
+pub fn with_remaining(mut instruction: Instruction, remaining: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(remaining.iter().map(|key| AccountMeta::new_readonly(*key, false)));
+    instruction
+}
+
+/// Replace every occurrence of an account in an instruction, e.g. to sign with someone else
+pub fn replace_account(mut instruction: Instruction, from: &Pubkey, to: &Pubkey) -> Instruction {
+    for meta in instruction.accounts.iter_mut().filter(|meta| meta.pubkey == *from) {
+        meta.pubkey = *to;
+    }
+    instruction
+}
+
+/// Assert that a transaction failed with the given program error
+pub fn assert_error(result: std::result::Result<(), BanksClientError>, expected: impl Into<u32>) {
+    let expected = expected.into();
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, InstructionError::Custom(code)))) => {
+            assert_eq!(code, expected, "expected error {expected}, got {code}");
+        },
+        other => panic!("expected error {expected}, got {other:?}"),
+    }
+}
+
+// PDAs
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &::badge_rewards::ID).0
+}
+
+pub fn event_authority() -> Pubkey {
+    pda(&[b"__event_authority"])
+}
+
+pub fn config_pda() -> Pubkey {
+    pda(&[b"config"])
+}
+
+pub fn badge_pda(owner: &Pubkey, badge_id: u64) -> Pubkey {
+    pda(&[b"badge", owner.as_ref(), &badge_id.to_le_bytes()])
+}
+
+pub fn reward_pda(owner: &Pubkey, badge_id: u64, reward_id: u64) -> Pubkey {
+    pda(&[b"reward", owner.as_ref(), &badge_id.to_le_bytes(), &reward_id.to_le_bytes()])
+}
+
+pub fn user_badge_pda(user: &Pubkey, badge_id: u64) -> Pubkey {
+    pda(&[b"user_badge", user.as_ref(), &badge_id.to_le_bytes()])
+}
+
+pub fn user_reward_pda(user: &Pubkey, reward_id: u64) -> Pubkey {
+    pda(&[b"user_reward", user.as_ref(), &reward_id.to_le_bytes()])
+}
+
+pub fn user_points_pda(owner: &Pubkey, user: &Pubkey) -> Pubkey {
+    pda(&[b"user_points", owner.as_ref(), user.as_ref()])
+}
+
+pub fn revoked_badge_pda(badge: &Pubkey, user: &Pubkey) -> Pubkey {
+    pda(&[b"revoked_badge", badge.as_ref(), user.as_ref()])
+}
+
+pub fn closed_badge_pda(badge: &Pubkey) -> Pubkey {
+    pda(&[b"closed_badge", badge.as_ref()])
+}
+
+pub fn badge_mint_pda(badge: &Pubkey, user: &Pubkey) -> Pubkey {
+    pda(&[b"badge_mint", badge.as_ref(), user.as_ref()])
+}
+
+pub fn reward_vault_pda(reward: &Pubkey) -> Pubkey {
+    pda(&[b"reward_vault", reward.as_ref()])
+}
+
+pub fn yield_vault_pda(badge: &Pubkey) -> Pubkey {
+    pda(&[b"yield_vault", badge.as_ref()])
+}
+
+pub fn vesting_escrow_pda(reward: &Pubkey, user: &Pubkey) -> Pubkey {
+    pda(&[b"vesting_escrow", reward.as_ref(), user.as_ref()])
+}
+
+pub fn vesting_vault_pda(reward: &Pubkey, user: &Pubkey) -> Pubkey {
+    pda(&[b"vesting_vault", reward.as_ref(), user.as_ref()])
+}
+
+pub fn reward_escrow_pda(user_reward: &Pubkey) -> Pubkey {
+    pda(&[b"user_reward_escrow", user_reward.as_ref()])
+}
+
+pub fn collection_pda(owner: &Pubkey, collection_id: u64) -> Pubkey {
+    pda(&[b"collection", owner.as_ref(), &collection_id.to_le_bytes()])
+}
+
+pub fn token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &TOKEN_PROGRAM)
+}
+
+// Instruction data defaults
+
+pub fn badge_args(badge_id: u64) -> instruction::InitializeBadge {
+    instruction::InitializeBadge {
+        badge_id,
+        name: "Early Adopter".to_string(),
+        description: "Joined in the first month".to_string(),
+        icon_uri: "https://example.com/badge.png".to_string(),
+        required_points: 10,
+        max_earnings: 100,
+        is_active: true,
+        mint_nft: false,
+        valid_for_seconds: None,
+        tiers: vec![],
+        prerequisites: vec![],
+    }
+}
+
+pub fn reward_args(badge_id: u64, reward_id: u64) -> instruction::InitializeReward {
+    instruction::InitializeReward {
+        badge_id,
+        reward_id,
+        name: "Discount".to_string(),
+        description: "10% off".to_string(),
+        reward_type: 2,
+        reward_value: 1,
+        token_mint: None,
+        nft_mint: None,
+        is_active: true,
+        min_tier: 0,
+        max_claims: None,
+        claim_start: None,
+        claim_end: None,
+        vesting: None,
+    }
+}
+
+pub fn token_reward_args(badge_id: u64, reward_id: u64, mint: &Pubkey, reward_value: u64) -> instruction::InitializeReward {
+    instruction::InitializeReward {
+        reward_type: 0,
+        reward_value,
+        token_mint: Some(*mint),
+        ..reward_args(badge_id, reward_id)
+    }
+}
+
+pub fn reward_update(badge_id: u64, reward_id: u64) -> instruction::UpdateReward {
+    instruction::UpdateReward {
+        badge_id,
+        reward_id,
+        name: None,
+        description: None,
+        reward_type: None,
+        reward_value: None,
+        token_mint: None,
+        nft_mint: None,
+        is_active: None,
+        min_tier: None,
+        max_claims: None,
+        claim_start: None,
+        claim_end: None,
+        vesting: None,
+    }
+}
+
+pub struct Env {
+    pub ctx: ProgramTestContext,
+    pub payer: Pubkey,
+    pub admin: Keypair,
+    pub mint_authority: Keypair,
+    nonce: u32,
+}
+
+impl Env {
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new("badge_rewards", ::badge_rewards::ID, processor!(process));
+        // initialize_config needs the upgradeable loader, so seed the config directly
+        let admin = Keypair::new();
+        let config = Config {
+            version: CONFIG_VERSION,
+            admin: admin.pubkey(),
+            paused: false,
+            protocol_fee_bps: 0,
+            bump: Pubkey::find_program_address(&[b"config"], &::badge_rewards::ID).1,
+        };
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        program_test.add_account(config_pda(), SdkAccount {
+            lamports: 1_000_000_000,
+            data,
+            owner: ::badge_rewards::ID,
+            executable: false,
+            rent_epoch: 0,
+        });
+        let ctx = program_test.start_with_context().await;
+        let payer = ctx.payer.pubkey();
+        Self { ctx, payer, admin, mint_authority: Keypair::new(), nonce: 0 }
+    }
+
+    /// Send instructions paid for by the test payer; each transaction gets a unique compute
+    /// budget so repeating the same instructions is never deduplicated
+    pub async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> std::result::Result<(), BanksClientError> {
+        self.nonce += 1;
+        let mut all = vec![ComputeBudgetInstruction::set_compute_unit_limit(1_400_000 - self.nonce)];
+        all.extend_from_slice(instructions);
+        let mut keypairs = vec![&self.ctx.payer];
+        keypairs.extend_from_slice(signers);
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(&all, Some(&self.payer), &keypairs, blockhash);
+        self.ctx.banks_client.process_transaction(transaction).await
+    }
+
+    /// A new keypair holding enough lamports to receive rent refunds
+    pub async fn keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        let transfer = system_instruction::transfer(&self.payer, &keypair.pubkey(), 1_000_000_000);
+        self.send(&[transfer], &[]).await.unwrap();
+        keypair
+    }
+
+    pub async fn exists(&mut self, key: &Pubkey) -> bool {
+        self.ctx.banks_client.get_account(*key).await.unwrap().is_some()
+    }
+
+    pub async fn lamports(&mut self, key: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*key).await.unwrap()
+    }
+
+    pub async fn state<T: AccountDeserialize>(&mut self, key: &Pubkey) -> T {
+        let account = self.ctx.banks_client.get_account(*key).await.unwrap().expect("account not found");
+        T::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    /// Overwrite a program account with `value`, as if it had been written by an older program
+    pub async fn set_state<T: AccountSerialize>(&mut self, key: &Pubkey, value: &T) {
+        let mut account = self.ctx.banks_client.get_account(*key).await.unwrap().expect("account not found");
+        let mut data = Vec::new();
+        value.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.ctx.set_account(key, &account.into());
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+    }
+
+    /// Move the clock forward by `seconds`
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    // Tokens
+
+    /// Create a Token-2022 mint, optionally charging a transfer fee of `fee_bps`
+    pub async fn create_mint(&mut self, decimals: u8, fee_bps: Option<u16>) -> Pubkey {
+        let mint = Keypair::new();
+        let authority = self.mint_authority.pubkey();
+        let extensions: &[ExtensionType] = if fee_bps.is_some() { &[ExtensionType::TransferFeeConfig] } else { &[] };
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions).unwrap();
+        let lamports = self.ctx.banks_client.get_rent().await.unwrap().minimum_balance(space);
+        let mut instructions = vec![system_instruction::create_account(&self.payer, &mint.pubkey(), lamports, space as u64, &TOKEN_PROGRAM)];
+        if let Some(fee_bps) = fee_bps {
+            instructions.push(transfer_fee::instruction::initialize_transfer_fee_config(
+                &TOKEN_PROGRAM,
+                &mint.pubkey(),
+                Some(&authority),
+                Some(&authority),
+                fee_bps,
+                u64::MAX,
+            ).unwrap());
+        }
+        instructions.push(spl_token_2022::instruction::initialize_mint2(&TOKEN_PROGRAM, &mint.pubkey(), &authority, None, decimals).unwrap());
+        self.send(&instructions, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// Create `owner`'s associated token account for `mint`, returning its address
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let create = spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &self.payer,
+            owner,
+            mint,
+            &TOKEN_PROGRAM,
+        );
+        self.send(&[create], &[]).await.unwrap();
+        token_account(owner, mint)
+    }
+
+    pub async fn mint_to(&mut self, mint: &Pubkey, account: &Pubkey, amount: u64) {
+        let mint_to = spl_token_2022::instruction::mint_to(&TOKEN_PROGRAM, mint, account, &self.mint_authority.pubkey(), &[], amount).unwrap();
+        let mint_authority = self.mint_authority.insecure_clone();
+        self.send(&[mint_to], &[&mint_authority]).await.unwrap();
+    }
+
+    /// Create `owner`'s token account for `mint` and fund it with `amount`
+    pub async fn fund_token_account(&mut self, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+        let account = self.create_token_account(owner, mint).await;
+        self.mint_to(mint, &account, amount).await;
+        account
+    }
+
+    pub async fn balance(&mut self, account: &Pubkey) -> u64 {
+        let account = self.ctx.banks_client.get_account(*account).await.unwrap().expect("token account not found");
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).unwrap().base.amount
+    }
+
+    /// Transfer fees withheld in a token account
+    pub async fn withheld(&mut self, account: &Pubkey) -> u64 {
+        let account = self.ctx.banks_client.get_account(*account).await.unwrap().expect("token account not found");
+        let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data).unwrap();
+        state.get_extension::<transfer_fee::TransferFeeAmount>().map_or(0, |fee| u64::from(fee.withheld_amount))
+    }
+
+    // Fixtures
+
+    pub async fn create_badge(&mut self, owner: &Keypair, args: instruction::InitializeBadge) -> Pubkey {
+        let badge_id = args.badge_id;
+        let instruction = self.initialize_badge(&owner.pubkey(), args);
+        self.send(&[instruction], &[owner]).await.unwrap();
+        badge_pda(&owner.pubkey(), badge_id)
+    }
+
+    /// Award `user` the badge's required points and stake the badge to them
+    pub async fn earn_badge(&mut self, owner: &Keypair, badge_id: u64, user: &Keypair) {
+        let badge: ::badge_rewards::Badge = self.state(&badge_pda(&owner.pubkey(), badge_id)).await;
+        let award = self.award_points(&owner.pubkey(), &user.pubkey(), badge.required_points.max(1));
+        let stake = self.stake_badge(&owner.pubkey(), badge_id, &user.pubkey(), false);
+        self.send(&[award, stake], &[owner, user]).await.unwrap();
+    }
+
+    /// Create a reward, with a vault for its mint when `mint` is given
+    pub async fn create_reward(&mut self, owner: &Keypair, args: instruction::InitializeReward, mint: Option<&Pubkey>) -> Pubkey {
+        let reward = reward_pda(&owner.pubkey(), args.badge_id, args.reward_id);
+        let instruction = self.initialize_reward(&owner.pubkey(), &owner.pubkey(), args, mint, None);
+        self.send(&[instruction], &[owner]).await.unwrap();
+        reward
+    }
+
+    // Instruction builders
+
+    pub fn initialize_badge(&self, owner: &Pubkey, args: instruction::InitializeBadge) -> Instruction {
+        let badge = badge_pda(owner, args.badge_id);
+        ix(event_accounts!(InitializeBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            closed_badge: closed_badge_pda(&badge),
+            owner: *owner,
+            system_program: system_program::ID,
+        }), args)
+    }
+
+    pub fn update_badge(&self, owner: &Pubkey, args: instruction::UpdateBadge) -> Instruction {
+        ix(event_accounts!(UpdateBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, args.badge_id),
+            owner: *owner,
+            system_program: system_program::ID,
+        }), args)
+    }
+
+    pub fn freeze_badge(&self, owner: &Pubkey, badge_id: u64) -> Instruction {
+        ix(event_accounts!(FreezeBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, badge_id),
+            badge_owner: *owner,
+        }), instruction::FreezeBadge { badge_id })
+    }
+
+    pub fn award_points(&self, owner: &Pubkey, user: &Pubkey, amount: u64) -> Instruction {
+        ix(event_accounts!(AwardPoints {
+            fee_payer: self.payer,
+            config: config_pda(),
+            user_points: user_points_pda(owner, user),
+            owner: *owner,
+            system_program: system_program::ID,
+        }), instruction::AwardPoints { user: *user, amount })
+    }
+
+    pub fn deduct_points(&self, owner: &Pubkey, user: &Pubkey, amount: u64) -> Instruction {
+        ix(event_accounts!(DeductPoints {
+            fee_payer: self.payer,
+            config: config_pda(),
+            user_points: user_points_pda(owner, user),
+            owner: *owner,
+        }), instruction::DeductPoints { user: *user, amount })
+    }
+
+    /// Stake a badge to `user`, signed by the badge owner; `mint_nft` passes the soulbound NFT accounts
+    pub fn stake_badge(&self, owner: &Pubkey, badge_id: u64, user: &Pubkey, mint_nft: bool) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        let badge_mint = badge_mint_pda(&badge, user);
+        ix(event_accounts!(StakeBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            user_badge: user_badge_pda(user, badge_id),
+            revoked_badge: revoked_badge_pda(&badge, user),
+            user_points: user_points_pda(owner, user),
+            badge_owner: *owner,
+            user: *user,
+            badge_mint: mint_nft.then_some(badge_mint),
+            user_token_account: mint_nft.then(|| token_account(user, &badge_mint)),
+            system_program: system_program::ID,
+            token_program: mint_nft.then_some(TOKEN_PROGRAM),
+            associated_token_program: mint_nft.then_some(anchor_spl::associated_token::ID),
+        }), instruction::StakeBadge { badge_id })
+    }
+
+    /// Unstake a badge, passing the soulbound NFT accounts when `mint_nft` and the yield
+    /// accounts when `yield_mint` is given
+    pub fn unstake_badge(&self, owner: &Pubkey, badge_id: u64, user: &Pubkey, mint_nft: bool, yield_mint: Option<&Pubkey>) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        let badge_mint = badge_mint_pda(&badge, user);
+        ix(event_accounts!(UnstakeBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            user_badge: user_badge_pda(user, badge_id),
+            badge_owner: *owner,
+            user: *user,
+            rent_recipient: self.payer,
+            badge_mint: mint_nft.then_some(badge_mint),
+            user_token_account: mint_nft.then(|| token_account(user, &badge_mint)),
+            system_program: system_program::ID,
+            token_program: mint_nft.then_some(TOKEN_PROGRAM),
+            yield_mint: yield_mint.copied(),
+            yield_vault: yield_mint.map(|_| yield_vault_pda(&badge)),
+            user_yield_account: yield_mint.map(|mint| token_account(user, mint)),
+            yield_token_program: yield_mint.map(|_| TOKEN_PROGRAM),
+        }), instruction::UnstakeBadge { badge_id })
+    }
+
+    pub fn revoke_badge(&self, owner: &Pubkey, badge_id: u64, user: &Pubkey, allow_reissue: bool, mint_nft: bool) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        let badge_mint = badge_mint_pda(&badge, user);
+        ix(event_accounts!(RevokeBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            user_badge: user_badge_pda(user, badge_id),
+            revoked_badge: revoked_badge_pda(&badge, user),
+            badge_owner: *owner,
+            user: *user,
+            rent_recipient: self.payer,
+            badge_mint: mint_nft.then_some(badge_mint),
+            user_token_account: mint_nft.then(|| token_account(user, &badge_mint)),
+            system_program: system_program::ID,
+            token_program: mint_nft.then_some(TOKEN_PROGRAM),
+        }), instruction::RevokeBadge {
+            badge_id,
+            reason_code: 1,
+            evidence_uri: Some("https://example.com/evidence".to_string()),
+            allow_reissue,
+        })
+    }
+
+    /// Bind a migrated user badge to `owner`'s badge; `user_signs` passes the user as co-signer
+    pub fn backfill_user_badge(&self, owner: &Pubkey, badge_id: u64, authority: &Pubkey, user: &Pubkey, user_signs: bool) -> Instruction {
+        ix(event_accounts!(BackfillUserBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, badge_id),
+            user_badge: user_badge_pda(user, badge_id),
+            authority: *authority,
+            user_signer: user_signs.then_some(*user),
+        }), instruction::BackfillUserBadge { badge_id, user: *user })
+    }
+
+    pub fn renew_badge(&self, owner: &Pubkey, badge_id: u64, user: &Pubkey) -> Instruction {
+        ix(event_accounts!(RenewBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, badge_id),
+            user_badge: user_badge_pda(user, badge_id),
+            badge_owner: *owner,
+        }), instruction::RenewBadge { badge_id, user: *user })
+    }
+
+    pub fn upgrade_badge(&self, owner: &Pubkey, badge_id: u64, user: &Pubkey) -> Instruction {
+        ix(event_accounts!(UpgradeBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, badge_id),
+            user_badge: user_badge_pda(user, badge_id),
+            user_points: user_points_pda(owner, user),
+            user: *user,
+        }), instruction::UpgradeBadge { badge_id })
+    }
+
+    pub fn close_badge(&self, owner: &Pubkey, badge_id: u64, yield_mint: Option<&Pubkey>) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        ix(event_accounts!(CloseBadge {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            badge_owner: *owner,
+            closed_badge: closed_badge_pda(&badge),
+            yield_mint: yield_mint.copied(),
+            yield_vault: yield_mint.map(|_| yield_vault_pda(&badge)),
+            token_program: yield_mint.map(|_| TOKEN_PROGRAM),
+            system_program: system_program::ID,
+        }), instruction::CloseBadge { badge_id })
+    }
+
+    /// Create a reward signed by `authority`; a mint creates the reward vault, and for an NFT
+    /// reward `authority_token_account` is escrowed into it
+    pub fn initialize_reward(
+        &self,
+        owner: &Pubkey,
+        authority: &Pubkey,
+        args: instruction::InitializeReward,
+        mint: Option<&Pubkey>,
+        authority_token_account: Option<Pubkey>,
+    ) -> Instruction {
+        let reward = reward_pda(owner, args.badge_id, args.reward_id);
+        ix(event_accounts!(InitializeReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, args.badge_id),
+            reward,
+            authority: *authority,
+            reward_mint: mint.copied(),
+            authority_token_account,
+            reward_vault: mint.map(|_| reward_vault_pda(&reward)),
+            system_program: system_program::ID,
+            token_program: mint.map(|_| TOKEN_PROGRAM),
+        }), args)
+    }
+
+    pub fn update_reward(&self, owner: &Pubkey, args: instruction::UpdateReward) -> Instruction {
+        let reward = reward_pda(owner, args.badge_id, args.reward_id);
+        ix(event_accounts!(UpdateReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, args.badge_id),
+            reward,
+            reward_vault: reward_vault_pda(&reward),
+            owner: *owner,
+            system_program: system_program::ID,
+        }), args)
+    }
+
+    /// Claim a reward; token rewards pay into the user's token account for `mint`, or into
+    /// the vesting escrow when `vesting`
+    #[allow(clippy::too_many_arguments)]
+    pub fn claim_reward(
+        &self,
+        owner: &Pubkey,
+        badge_id: u64,
+        reward_id: u64,
+        user: &Pubkey,
+        reward_type: u8,
+        reward_value: u64,
+        mint: Option<&Pubkey>,
+        vesting: bool,
+    ) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        let reward = reward_pda(owner, badge_id, reward_id);
+        ix(event_accounts!(ClaimReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            reward,
+            user_reward: user_reward_pda(user, reward_id),
+            user_badge: user_badge_pda(user, badge_id),
+            revoked_badge: revoked_badge_pda(&badge, user),
+            user_token_account: mint.filter(|_| !vesting).map(|mint| token_account(user, mint)),
+            reward_token_account: mint.map(|_| reward_vault_pda(&reward)),
+            reward_mint: mint.copied(),
+            vesting_escrow: vesting.then(|| vesting_escrow_pda(&reward, user)),
+            vesting_vault: vesting.then(|| vesting_vault_pda(&reward, user)),
+            user: *user,
+            system_program: system_program::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: TOKEN_PROGRAM,
+        }), instruction::ClaimReward { owner: *owner, badge_id, reward_id, reward_type, reward_value })
+    }
+
+    pub fn fund_reward(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, mint: &Pubkey, amount: u64) -> Instruction {
+        let reward = reward_pda(owner, badge_id, reward_id);
+        ix(event_accounts!(FundReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward,
+            owner: *owner,
+            reward_mint: *mint,
+            owner_token_account: token_account(owner, mint),
+            reward_vault: reward_vault_pda(&reward),
+            system_program: system_program::ID,
+            token_program: TOKEN_PROGRAM,
+        }), instruction::FundReward { badge_id, reward_id, amount })
+    }
+
+    pub fn withdraw_reward(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, mint: &Pubkey, amount: u64) -> Instruction {
+        let reward = reward_pda(owner, badge_id, reward_id);
+        ix(event_accounts!(WithdrawReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward,
+            owner: *owner,
+            reward_mint: *mint,
+            owner_token_account: token_account(owner, mint),
+            reward_vault: reward_vault_pda(&reward),
+            token_program: TOKEN_PROGRAM,
+        }), instruction::WithdrawReward { badge_id, reward_id, amount })
+    }
+
+    pub fn sweep_expired_reward(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, mint: &Pubkey) -> Instruction {
+        let reward = reward_pda(owner, badge_id, reward_id);
+        ix(event_accounts!(SweepExpiredReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward,
+            reward_mint: *mint,
+            authority_token_account: token_account(owner, mint),
+            reward_vault: reward_vault_pda(&reward),
+            token_program: TOKEN_PROGRAM,
+        }), instruction::SweepExpiredReward { badge_id, reward_id })
+    }
+
+    pub fn close_reward(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, mint: Option<&Pubkey>, sweep: bool) -> Instruction {
+        let reward = reward_pda(owner, badge_id, reward_id);
+        ix(event_accounts!(CloseReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward,
+            owner: *owner,
+            reward_vault: mint.map(|_| reward_vault_pda(&reward)),
+            reward_mint: mint.copied(),
+            owner_token_account: mint.map(|mint| token_account(owner, mint)),
+            token_program: mint.map(|_| TOKEN_PROGRAM),
+        }), instruction::CloseReward { badge_id, reward_id, sweep })
+    }
+
+    pub fn stake_reward(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, min_stake_period: u64) -> Instruction {
+        ix(event_accounts!(StakeReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward: reward_pda(owner, badge_id, reward_id),
+            business_owner: *owner,
+        }), instruction::StakeReward { badge_id, reward_id, min_stake_period })
+    }
+
+    pub fn unstake_reward(&self, owner: &Pubkey, badge_id: u64, reward_id: u64) -> Instruction {
+        ix(event_accounts!(UnstakeReward {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward: reward_pda(owner, badge_id, reward_id),
+            business_owner: *owner,
+        }), instruction::UnstakeReward { badge_id, reward_id })
+    }
+
+    /// Lock a claimed reward; `mint` passes the player's token account and a new escrow
+    pub fn stake_reward_player(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, player: &Pubkey, mint: Option<&Pubkey>, min_stake_period: u64) -> Instruction {
+        let user_reward = user_reward_pda(player, reward_id);
+        ix(event_accounts!(StakeRewardPlayer {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward: reward_pda(owner, badge_id, reward_id),
+            user_reward,
+            player: *player,
+            reward_mint: mint.copied(),
+            player_token_account: mint.map(|mint| token_account(player, mint)),
+            reward_escrow: mint.map(|_| reward_escrow_pda(&user_reward)),
+            system_program: system_program::ID,
+            token_program: mint.map(|_| TOKEN_PROGRAM),
+        }), instruction::StakeRewardPlayer { owner: *owner, badge_id, reward_id, min_stake_period })
+    }
+
+    pub fn unstake_reward_player(&self, reward_id: u64, player: &Pubkey, mint: Option<&Pubkey>) -> Instruction {
+        let user_reward = user_reward_pda(player, reward_id);
+        ix(event_accounts!(UnstakeRewardPlayer {
+            fee_payer: self.payer,
+            config: config_pda(),
+            user_reward,
+            player: *player,
+            reward_mint: mint.copied(),
+            player_token_account: mint.map(|mint| token_account(player, mint)),
+            reward_escrow: mint.map(|_| reward_escrow_pda(&user_reward)),
+            token_program: mint.map(|_| TOKEN_PROGRAM),
+        }), instruction::UnstakeRewardPlayer { reward_id })
+    }
+
+    pub fn withdraw_vested(&self, reward: &Pubkey, user: &Pubkey, mint: &Pubkey, badge: &Pubkey) -> Instruction {
+        ix(event_accounts!(WithdrawVested {
+            fee_payer: self.payer,
+            config: config_pda(),
+            vesting_escrow: vesting_escrow_pda(reward, user),
+            vesting_vault: vesting_vault_pda(reward, user),
+            revoked_badge: revoked_badge_pda(badge, user),
+            reward_mint: *mint,
+            user_token_account: token_account(user, mint),
+            user: *user,
+            token_program: TOKEN_PROGRAM,
+        }), instruction::WithdrawVested {})
+    }
+
+    pub fn reclaim_unvested(&self, reward: &Pubkey, user: &Pubkey, mint: &Pubkey, badge: &Pubkey) -> Instruction {
+        ix(event_accounts!(ReclaimUnvested {
+            fee_payer: self.payer,
+            config: config_pda(),
+            vesting_escrow: vesting_escrow_pda(reward, user),
+            vesting_vault: vesting_vault_pda(reward, user),
+            revoked_badge: revoked_badge_pda(badge, user),
+            reward_mint: *mint,
+            reward_vault: reward_vault_pda(reward),
+            token_program: TOKEN_PROGRAM,
+        }), instruction::ReclaimUnvested {})
+    }
+
+    pub fn set_badge_yield(&self, owner: &Pubkey, badge_id: u64, mint: &Pubkey, emission_rate: u64) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        ix(event_accounts!(SetBadgeYield {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            owner: *owner,
+            yield_mint: *mint,
+            yield_vault: yield_vault_pda(&badge),
+            system_program: system_program::ID,
+            token_program: TOKEN_PROGRAM,
+        }), instruction::SetBadgeYield { badge_id, emission_rate })
+    }
+
+    pub fn fund_badge_yield(&self, owner: &Pubkey, badge_id: u64, funder: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        ix(event_accounts!(FundBadgeYield {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            funder: *funder,
+            yield_mint: *mint,
+            funder_token_account: token_account(funder, mint),
+            yield_vault: yield_vault_pda(&badge),
+            token_program: TOKEN_PROGRAM,
+        }), instruction::FundBadgeYield { badge_id, amount })
+    }
+
+    pub fn harvest(&self, owner: &Pubkey, badge_id: u64, user: &Pubkey, mint: &Pubkey) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        ix(event_accounts!(Harvest {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            user_badge: user_badge_pda(user, badge_id),
+            yield_mint: *mint,
+            yield_vault: yield_vault_pda(&badge),
+            user_token_account: token_account(user, mint),
+            user: *user,
+            token_program: TOKEN_PROGRAM,
+        }), instruction::Harvest { badge_id })
+    }
+
+    pub fn withdraw_badge_yield(&self, owner: &Pubkey, badge_id: u64, mint: &Pubkey, amount: u64) -> Instruction {
+        let badge = badge_pda(owner, badge_id);
+        ix(event_accounts!(WithdrawBadgeYield {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge,
+            owner: *owner,
+            yield_mint: *mint,
+            owner_token_account: token_account(owner, mint),
+            yield_vault: yield_vault_pda(&badge),
+            token_program: TOKEN_PROGRAM,
+        }), instruction::WithdrawBadgeYield { badge_id, amount })
+    }
+
+    pub fn initialize_collection(&self, owner: &Pubkey, collection_id: u64, badge_ids: Vec<u64>, meta_badge_id: u64) -> Instruction {
+        ix(event_accounts!(InitializeCollection {
+            fee_payer: self.payer,
+            config: config_pda(),
+            meta_badge: badge_pda(owner, meta_badge_id),
+            collection: collection_pda(owner, collection_id),
+            owner: *owner,
+            system_program: system_program::ID,
+        }), instruction::InitializeCollection {
+            collection_id,
+            name: "Founders".to_string(),
+            badge_ids,
+            meta_badge_id,
+        })
+    }
+
+    /// Complete a collection, passing `user`'s member badges as remaining accounts
+    pub fn complete_collection(&self, owner: &Pubkey, collection_id: u64, meta_badge_id: u64, user: &Pubkey, badge_ids: &[u64]) -> Instruction {
+        let meta_badge = badge_pda(owner, meta_badge_id);
+        let members: Vec<Pubkey> = badge_ids.iter().map(|badge_id| user_badge_pda(user, *badge_id)).collect();
+        with_remaining(ix(event_accounts!(CompleteCollection {
+            fee_payer: self.payer,
+            config: config_pda(),
+            collection: collection_pda(owner, collection_id),
+            meta_badge,
+            user_badge: user_badge_pda(user, meta_badge_id),
+            revoked_badge: revoked_badge_pda(&meta_badge, user),
+            user_points: user_points_pda(owner, user),
+            user: *user,
+            system_program: system_program::ID,
+        }), instruction::CompleteCollection { collection_id }), &members)
+    }
+
+    pub fn set_pause(&self, admin: &Pubkey, paused: bool) -> Instruction {
+        ix(event_accounts!(SetPause {
+            fee_payer: self.payer,
+            config: config_pda(),
+            admin: *admin,
+        }), instruction::SetPause { paused })
+    }
+
+    pub fn transfer_admin(&self, admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+        ix(event_accounts!(TransferAdmin {
+            fee_payer: self.payer,
+            config: config_pda(),
+            admin: *admin,
+        }), instruction::TransferAdmin { new_admin: *new_admin })
+    }
+
+    pub fn set_protocol_fee(&self, admin: &Pubkey, protocol_fee_bps: u16) -> Instruction {
+        ix(event_accounts!(SetProtocolFee {
+            fee_payer: self.payer,
+            config: config_pda(),
+            admin: *admin,
+        }), instruction::SetProtocolFee { protocol_fee_bps })
+    }
+
+    pub fn propose_badge_owner(&self, owner: &Pubkey, badge_id: u64, authority: &Pubkey, new_authority: Option<Pubkey>) -> Instruction {
+        ix(event_accounts!(ProposeBadgeOwner {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, badge_id),
+            authority: *authority,
+        }), instruction::ProposeBadgeOwner { badge_id, new_authority })
+    }
+
+    pub fn accept_badge_owner(&self, owner: &Pubkey, badge_id: u64, new_authority: &Pubkey) -> Instruction {
+        ix(event_accounts!(AcceptBadgeOwner {
+            fee_payer: self.payer,
+            config: config_pda(),
+            badge: badge_pda(owner, badge_id),
+            new_authority: *new_authority,
+        }), instruction::AcceptBadgeOwner { badge_id })
+    }
+
+    pub fn propose_reward_owner(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, authority: &Pubkey, new_authority: Option<Pubkey>) -> Instruction {
+        ix(event_accounts!(ProposeRewardOwner {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward: reward_pda(owner, badge_id, reward_id),
+            authority: *authority,
+        }), instruction::ProposeRewardOwner { badge_id, reward_id, new_authority })
+    }
+
+    pub fn accept_reward_owner(&self, owner: &Pubkey, badge_id: u64, reward_id: u64, new_authority: &Pubkey) -> Instruction {
+        ix(event_accounts!(AcceptRewardOwner {
+            fee_payer: self.payer,
+            config: config_pda(),
+            reward: reward_pda(owner, badge_id, reward_id),
+            new_authority: *new_authority,
+        }), instruction::AcceptRewardOwner { badge_id, reward_id })
+    }
+}
\ No newline at end of file
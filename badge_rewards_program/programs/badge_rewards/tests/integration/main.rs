@@ -0,0 +1,10 @@
+//! Program tests, run natively against a solana-program-test bank with the Token-2022 and
+//! associated token programs loaded
+
+mod admin;
+mod badges;
+mod collections;
+mod common;
+mod rewards;
+mod vesting;
+mod yields;
\ No newline at end of file
@@ -0,0 +1,391 @@
+use crate::common::*;
+use ::badge_rewards::{instruction, BadgeRewardsError, BadgeTier, Reward, StakeStatus, UserReward};
+use anchor_lang::prelude::Pubkey;
+use solana_program_test::tokio;
+use solana_sdk::{signature::Keypair, signer::Signer as _};
+
+/// A funded token reward of `reward_value` on badge 1, with `user` holding the badge and a token account
+async fn token_reward(env: &mut Env, owner: &Keypair, user: &Keypair, args: instruction::InitializeReward, funding: u64) -> Pubkey {
+    let mint = args.token_mint.unwrap();
+    env.create_badge(owner, badge_args(1)).await;
+    env.earn_badge(owner, 1, user).await;
+    env.create_reward(owner, args, Some(&mint)).await;
+    env.fund_token_account(&owner.pubkey(), &mint, funding).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+    let fund = env.fund_reward(&owner.pubkey(), 1, 1, &mint, funding);
+    env.send(&[fund], &[owner]).await.unwrap();
+    mint
+}
+
+#[tokio::test]
+async fn claim_token_reward_pays_from_vault() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 40), 100).await;
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 40, Some(&mint), false);
+    env.send(&[claim], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 40);
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+    assert_eq!(env.balance(&reward_vault_pda(&reward)).await, 60);
+    let user_reward: UserReward = env.state(&user_reward_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_reward.claimed_amount, 40);
+    assert_eq!(user_reward.reward, reward);
+    let reward: Reward = env.state(&reward).await;
+    assert_eq!(reward.total_claimed, 1);
+}
+
+#[tokio::test]
+async fn claim_reward_requires_badge_and_matching_terms() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 40), 30).await;
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 41, Some(&mint), false);
+    assert_error(env.send(&[claim], &[&user]).await, BadgeRewardsError::InvalidRewardValue);
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 40, Some(&mint), false);
+    assert_error(env.send(&[claim], &[&user]).await, BadgeRewardsError::InsufficientVaultBalance);
+
+    let outsider = Keypair::new();
+    env.create_token_account(&outsider.pubkey(), &mint).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &outsider.pubkey(), 0, 40, Some(&mint), false);
+    assert_error(env.send(&[claim], &[&outsider]).await, BadgeRewardsError::BadgeNotFound);
+}
+
+#[tokio::test]
+async fn claim_nft_reward_transfers_escrowed_nft() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let nft = env.create_mint(0, None).await;
+    let owner_nft_account = env.fund_token_account(&owner.pubkey(), &nft, 1).await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    let args = instruction::InitializeReward { reward_type: 1, nft_mint: Some(nft), ..reward_args(1, 1) };
+    let create = env.initialize_reward(&owner.pubkey(), &owner.pubkey(), args, Some(&nft), Some(owner_nft_account));
+    env.send(&[create], &[&owner]).await.unwrap();
+    assert_eq!(env.balance(&reward_vault_pda(&reward_pda(&owner.pubkey(), 1, 1))).await, 1);
+
+    let user_nft_account = env.create_token_account(&user.pubkey(), &nft).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 1, 1, Some(&nft), false);
+    env.send(&[claim], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&user_nft_account).await, 1);
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert!(!reward.is_active);
+}
+
+#[tokio::test]
+async fn claim_reward_respects_claim_window() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let early = Keypair::new();
+    let late = Keypair::new();
+    let now = env.now().await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &early).await;
+    env.earn_badge(&owner, 1, &late).await;
+    env.create_reward(&owner, instruction::InitializeReward {
+        claim_start: Some(now + 100),
+        claim_end: Some(now + 200),
+        ..reward_args(1, 1)
+    }, None).await;
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &early.pubkey(), 2, 1, None, false);
+    assert_error(env.send(&[claim], &[&early]).await, BadgeRewardsError::ClaimWindowNotOpen);
+    env.warp(150).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &early.pubkey(), 2, 1, None, false);
+    env.send(&[claim], &[&early]).await.unwrap();
+    env.warp(100).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &late.pubkey(), 2, 1, None, false);
+    assert_error(env.send(&[claim], &[&late]).await, BadgeRewardsError::ClaimWindowClosed);
+}
+
+#[tokio::test]
+async fn claim_reward_stops_at_max_claims() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let first = Keypair::new();
+    let second = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &first).await;
+    env.earn_badge(&owner, 1, &second).await;
+    env.create_reward(&owner, instruction::InitializeReward { max_claims: Some(1), ..reward_args(1, 1) }, None).await;
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &first.pubkey(), 2, 1, None, false);
+    env.send(&[claim], &[&first]).await.unwrap();
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert_eq!(reward.total_claimed, 1);
+    assert!(!reward.is_active);
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &second.pubkey(), 2, 1, None, false);
+    assert_error(env.send(&[claim], &[&second]).await, BadgeRewardsError::RewardNotActive);
+
+    // Raising the cap again reopens the reward
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward {
+        max_claims: Some(Some(2)),
+        is_active: Some(true),
+        ..reward_update(1, 1)
+    });
+    env.send(&[update], &[&owner]).await.unwrap();
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &second.pubkey(), 2, 1, None, false);
+    env.send(&[claim], &[&second]).await.unwrap();
+}
+
+#[tokio::test]
+async fn claim_reward_requires_min_tier() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let tier = BadgeTier { required_points: 20, icon_uri: "https://example.com/tier.png".to_string() };
+    env.create_badge(&owner, instruction::InitializeBadge { tiers: vec![tier], ..badge_args(1) }).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.create_reward(&owner, instruction::InitializeReward { min_tier: 1, ..reward_args(1, 1) }, None).await;
+    let create = env.initialize_reward(&owner.pubkey(), &owner.pubkey(), instruction::InitializeReward { min_tier: 2, ..reward_args(1, 2) }, None, None);
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::InvalidMinTier);
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { min_tier: Some(2), ..reward_update(1, 1) });
+    assert_error(env.send(&[update], &[&owner]).await, BadgeRewardsError::InvalidMinTier);
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 2, 1, None, false);
+    assert_error(env.send(&[claim], &[&user]).await, BadgeRewardsError::InsufficientTier);
+
+    let award = env.award_points(&owner.pubkey(), &user.pubkey(), 10);
+    let upgrade = env.upgrade_badge(&owner.pubkey(), 1, &user.pubkey());
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 2, 1, None, false);
+    env.send(&[award, upgrade, claim], &[&owner, &user]).await.unwrap();
+}
+
+#[tokio::test]
+async fn update_reward_keeps_claim_window_ordered() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let now = env.now().await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.create_reward(&owner, instruction::InitializeReward { claim_end: Some(now + 100), ..reward_args(1, 1) }, None).await;
+
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { claim_start: Some(Some(now + 100)), ..reward_update(1, 1) });
+    assert_error(env.send(&[update], &[&owner]).await, BadgeRewardsError::InvalidClaimWindow);
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward {
+        name: Some("A much longer reward name than before".to_string()),
+        claim_end: Some(None),
+        ..reward_update(1, 1)
+    });
+    env.send(&[update], &[&owner]).await.unwrap();
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert_eq!(reward.name, "A much longer reward name than before");
+    assert_eq!(reward.claim_end, None);
+
+    let stranger = Keypair::new();
+    let update = replace_account(env.update_reward(&owner.pubkey(), reward_update(1, 1)), &owner.pubkey(), &stranger.pubkey());
+    assert_error(env.send(&[update], &[&stranger]).await, BadgeRewardsError::Unauthorized);
+}
+
+#[tokio::test]
+async fn update_reward_locks_type_and_mints_once_funded_or_claimed() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.create_reward(&owner, reward_args(1, 1), None).await;
+    env.create_reward(&owner, token_reward_args(1, 2, &mint, 10), Some(&mint)).await;
+
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { reward_type: Some(1), nft_mint: Some(mint), ..reward_update(1, 1) });
+    env.send(&[update], &[&owner]).await.unwrap();
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { reward_type: Some(2), ..reward_update(1, 1) });
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 2, 1, None, false);
+    env.send(&[update, claim], &[&owner, &user]).await.unwrap();
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { reward_type: Some(0), token_mint: Some(mint), ..reward_update(1, 1) });
+    assert_error(env.send(&[update], &[&owner]).await, BadgeRewardsError::RewardTermsLocked);
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { reward_value: Some(5), ..reward_update(1, 1) });
+    env.send(&[update], &[&owner]).await.unwrap();
+
+    let other_mint = env.create_mint(6, None).await;
+    let update = env.update_reward(&owner.pubkey(), instruction::UpdateReward { token_mint: Some(other_mint), ..reward_update(1, 2) });
+    assert_error(env.send(&[update], &[&owner]).await, BadgeRewardsError::RewardTermsLocked);
+}
+
+#[tokio::test]
+async fn fund_and_withdraw_reward_vault() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 40), 100).await;
+
+    let withdraw = env.withdraw_reward(&owner.pubkey(), 1, 1, &mint, 101);
+    assert_error(env.send(&[withdraw], &[&owner]).await, BadgeRewardsError::InsufficientVaultBalance);
+    let withdraw = env.withdraw_reward(&owner.pubkey(), 1, 1, &mint, 70);
+    env.send(&[withdraw], &[&owner]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 70);
+    assert_eq!(env.balance(&reward_vault_pda(&reward_pda(&owner.pubkey(), 1, 1))).await, 30);
+
+    let fund = env.fund_reward(&owner.pubkey(), 1, 1, &mint, 0);
+    assert_error(env.send(&[fund], &[&owner]).await, BadgeRewardsError::InvalidAmount);
+}
+
+#[tokio::test]
+async fn sweep_expired_reward_after_claim_window() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    let now = env.now().await;
+    token_reward(&mut env, &owner, &user, instruction::InitializeReward {
+        claim_end: Some(now + 100),
+        ..token_reward_args(1, 1, &mint, 40)
+    }, 100).await;
+
+    let sweep = env.sweep_expired_reward(&owner.pubkey(), 1, 1, &mint);
+    assert_error(env.send(&[sweep], &[]).await, BadgeRewardsError::ClaimWindowNotClosed);
+    env.warp(100).await;
+    let sweep = env.sweep_expired_reward(&owner.pubkey(), 1, 1, &mint);
+    env.send(&[sweep], &[]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 100);
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert!(!reward.is_active);
+}
+
+#[tokio::test]
+async fn business_stake_holds_reward_until_period_ends() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.create_reward(&owner, instruction::InitializeReward { is_active: false, ..reward_args(1, 1) }, None).await;
+
+    let stake = env.stake_reward(&owner.pubkey(), 1, 1, 100);
+    env.send(&[stake], &[&owner]).await.unwrap();
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert_eq!(reward.stake_status, StakeStatus::StakedByBusiness);
+    let close = env.close_reward(&owner.pubkey(), 1, 1, None, false);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::RewardNotAvailable);
+
+    let unstake = env.unstake_reward(&owner.pubkey(), 1, 1);
+    assert_error(env.send(&[unstake], &[&owner]).await, BadgeRewardsError::MinStakePeriodNotMet);
+    env.warp(100).await;
+    let unstake = env.unstake_reward(&owner.pubkey(), 1, 1);
+    env.send(&[unstake], &[&owner]).await.unwrap();
+    let reward: Reward = env.state(&reward_pda(&owner.pubkey(), 1, 1)).await;
+    assert_eq!(reward.stake_status, StakeStatus::Available);
+    assert!(reward.is_active);
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 2, 1, None, false);
+    env.send(&[claim], &[&user]).await.unwrap();
+    let stake = env.stake_reward(&owner.pubkey(), 1, 1, 100);
+    assert_error(env.send(&[stake], &[&owner]).await, BadgeRewardsError::RewardAlreadyActive);
+}
+
+#[tokio::test]
+async fn player_stake_escrows_claimed_tokens() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = env.keypair().await;
+    let mint = env.create_mint(6, None).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 40), 100).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 40, Some(&mint), false);
+    env.send(&[claim], &[&user]).await.unwrap();
+
+    let stake = env.stake_reward_player(&owner.pubkey(), 1, 1, &user.pubkey(), Some(&mint), 100);
+    env.send(&[stake], &[&user]).await.unwrap();
+    let user_reward_key = user_reward_pda(&user.pubkey(), 1);
+    let escrow = reward_escrow_pda(&user_reward_key);
+    assert_eq!(env.balance(&escrow).await, 40);
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 0);
+    let user_reward: UserReward = env.state(&user_reward_key).await;
+    assert_eq!(user_reward.stake_status, StakeStatus::StakedByPlayer);
+    assert_eq!(user_reward.locked_amount, 40);
+
+    let unstake = env.unstake_reward_player(1, &user.pubkey(), Some(&mint));
+    assert_error(env.send(&[unstake], &[&user]).await, BadgeRewardsError::MinStakePeriodNotMet);
+    env.warp(100).await;
+    let unstake = env.unstake_reward_player(1, &user.pubkey(), Some(&mint));
+    env.send(&[unstake], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 40);
+    assert!(!env.exists(&escrow).await);
+    let user_reward: UserReward = env.state(&user_reward_key).await;
+    assert_eq!(user_reward.stake_status, StakeStatus::Available);
+    assert_eq!(user_reward.locked_amount, 0);
+}
+
+#[tokio::test]
+async fn player_escrow_with_withheld_fees_is_closed() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = env.keypair().await;
+    let mint = env.create_mint(6, Some(100)).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 100), 1_000).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 100, Some(&mint), false);
+    let stake = env.stake_reward_player(&owner.pubkey(), 1, 1, &user.pubkey(), Some(&mint), 0);
+    env.send(&[claim, stake], &[&user]).await.unwrap();
+    let escrow = reward_escrow_pda(&user_reward_pda(&user.pubkey(), 1));
+    assert_eq!(env.balance(&escrow).await, 99);
+    assert_eq!(env.withheld(&escrow).await, 1);
+
+    let unstake = env.unstake_reward_player(1, &user.pubkey(), Some(&mint));
+    env.send(&[unstake], &[&user]).await.unwrap();
+    assert!(!env.exists(&escrow).await);
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 98);
+}
+
+#[tokio::test]
+async fn player_stake_is_only_released_to_the_player() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.create_reward(&owner, reward_args(1, 1), None).await;
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 2, 1, None, false);
+    env.send(&[claim], &[&user]).await.unwrap();
+
+    let unstake = env.unstake_reward_player(1, &user.pubkey(), None);
+    assert_error(env.send(&[unstake], &[&user]).await, BadgeRewardsError::RewardNotStaked);
+    let stake = env.stake_reward_player(&owner.pubkey(), 1, 1, &user.pubkey(), None, 0);
+    env.send(&[stake], &[&user]).await.unwrap();
+    let stake = env.stake_reward_player(&owner.pubkey(), 1, 1, &user.pubkey(), None, 0);
+    assert_error(env.send(&[stake], &[&user]).await, BadgeRewardsError::RewardAlreadyStaked);
+}
+
+#[tokio::test]
+async fn close_reward_harvests_withheld_fees_from_the_vault() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, Some(100)).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 40), 1_000).await;
+    let vault = reward_vault_pda(&reward_pda(&owner.pubkey(), 1, 1));
+    assert_eq!(env.withheld(&vault).await, 10);
+
+    let close = env.close_reward(&owner.pubkey(), 1, 1, Some(&mint), true);
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert!(!env.exists(&vault).await);
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 980);
+}
+
+#[tokio::test]
+async fn close_reward_sweeps_vault_only_when_asked() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    token_reward(&mut env, &owner, &user, token_reward_args(1, 1, &mint, 40), 100).await;
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+
+    let close = env.close_reward(&owner.pubkey(), 1, 1, Some(&mint), false);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::VaultNotEmpty);
+    let close = env.close_reward(&owner.pubkey(), 1, 1, None, true);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::MissingRewardVault);
+
+    let close = env.close_reward(&owner.pubkey(), 1, 1, Some(&mint), true);
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 100);
+    assert!(!env.exists(&reward).await);
+    assert!(!env.exists(&reward_vault_pda(&reward)).await);
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+use crate::common::*;
+use ::badge_rewards::{instruction, BadgeRewardsError, VestingEscrow, VestingSchedule};
+use anchor_lang::prelude::Pubkey;
+use solana_program_test::tokio;
+use solana_sdk::{signature::Keypair, signer::Signer as _};
+
+const VESTING: VestingSchedule = VestingSchedule { cliff_seconds: 100, duration_seconds: 1_000 };
+
+/// A vesting token reward of 1000 on badge 1 that `user` has claimed, returning the mint
+async fn vesting_claim(env: &mut Env, owner: &Keypair, user: &Keypair, fee_bps: Option<u16>) -> Pubkey {
+    let mint = env.create_mint(6, fee_bps).await;
+    env.create_badge(owner, badge_args(1)).await;
+    env.earn_badge(owner, 1, user).await;
+    env.create_reward(owner, instruction::InitializeReward {
+        vesting: Some(VESTING),
+        ..token_reward_args(1, 1, &mint, 1_000)
+    }, Some(&mint)).await;
+    env.fund_token_account(&owner.pubkey(), &mint, 10_000).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+    let fund = env.fund_reward(&owner.pubkey(), 1, 1, &mint, 5_000);
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 1_000, Some(&mint), true);
+    env.send(&[fund, claim], &[owner, user]).await.unwrap();
+    mint
+}
+
+#[tokio::test]
+async fn vesting_claim_unlocks_linearly_after_cliff() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let claimed_at = env.now().await;
+    let mint = vesting_claim(&mut env, &owner, &user, None).await;
+    let badge = badge_pda(&owner.pubkey(), 1);
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+    let user_account = token_account(&user.pubkey(), &mint);
+
+    let escrow: VestingEscrow = env.state(&vesting_escrow_pda(&reward, &user.pubkey())).await;
+    assert_eq!(escrow.total_amount, 1_000);
+    assert_eq!(escrow.cliff_at, claimed_at + 100);
+    assert_eq!(escrow.end_at, claimed_at + 1_000);
+    assert_eq!(env.balance(&vesting_vault_pda(&reward, &user.pubkey())).await, 1_000);
+    assert_eq!(env.balance(&user_account).await, 0);
+
+    env.warp(99).await;
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    assert_error(env.send(&[withdraw], &[&user]).await, BadgeRewardsError::NothingVested);
+    env.warp(401).await;
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[withdraw], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&user_account).await, 500);
+    env.warp(600).await;
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[withdraw], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&user_account).await, 1_000);
+    let escrow: VestingEscrow = env.state(&vesting_escrow_pda(&reward, &user.pubkey())).await;
+    assert_eq!(escrow.withdrawn_amount, 1_000);
+}
+
+#[tokio::test]
+async fn vesting_withdrawals_cover_the_transfer_fee() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = vesting_claim(&mut env, &owner, &user, Some(100)).await;
+    let badge = badge_pda(&owner.pubkey(), 1);
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+    let vesting_vault = vesting_vault_pda(&reward, &user.pubkey());
+    let user_account = token_account(&user.pubkey(), &mint);
+    assert_eq!(env.balance(&vesting_vault).await, 1_011);
+
+    env.warp(500).await;
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[withdraw], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&user_account).await, 500);
+    env.warp(500).await;
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[withdraw], &[&user]).await.unwrap();
+    assert!(env.balance(&user_account).await >= 999);
+    assert_eq!(env.balance(&vesting_vault).await, 0);
+}
+
+#[tokio::test]
+async fn reclaim_keeps_the_fee_for_vested_tokens() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = vesting_claim(&mut env, &owner, &user, Some(100)).await;
+    let badge = badge_pda(&owner.pubkey(), 1);
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+
+    env.warp(400).await;
+    let revoke = env.revoke_badge(&owner.pubkey(), 1, &user.pubkey(), false, false);
+    env.send(&[revoke], &[&owner]).await.unwrap();
+    let reclaim = env.reclaim_unvested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[reclaim], &[]).await.unwrap();
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[withdraw], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 400);
+    assert_eq!(env.balance(&vesting_vault_pda(&reward, &user.pubkey())).await, 0);
+
+    env.warp(600).await;
+    let close = env.close_reward(&owner.pubkey(), 1, 1, Some(&mint), true);
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert!(!env.exists(&reward_vault_pda(&reward)).await);
+}
+
+#[tokio::test]
+async fn vesting_claim_requires_escrow_only_for_vesting_rewards() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = env.create_mint(6, None).await;
+    env.create_badge(&owner, badge_args(1)).await;
+    env.earn_badge(&owner, 1, &user).await;
+    env.create_reward(&owner, instruction::InitializeReward { vesting: Some(VESTING), ..token_reward_args(1, 1, &mint, 10) }, Some(&mint)).await;
+    env.create_reward(&owner, token_reward_args(1, 2, &mint, 10), Some(&mint)).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+
+    let claim = env.claim_reward(&owner.pubkey(), 1, 1, &user.pubkey(), 0, 10, Some(&mint), false);
+    assert_error(env.send(&[claim], &[&user]).await, BadgeRewardsError::MissingVestingEscrow);
+    let claim = env.claim_reward(&owner.pubkey(), 1, 2, &user.pubkey(), 0, 10, Some(&mint), true);
+    assert_error(env.send(&[claim], &[&user]).await, BadgeRewardsError::InvalidVestingEscrow);
+
+    let create = env.initialize_reward(&owner.pubkey(), &owner.pubkey(), instruction::InitializeReward {
+        vesting: Some(VestingSchedule { cliff_seconds: 10, duration_seconds: 5 }),
+        ..token_reward_args(1, 3, &mint, 10)
+    }, Some(&mint), None);
+    assert_error(env.send(&[create], &[&owner]).await, BadgeRewardsError::InvalidVestingSchedule);
+}
+
+#[tokio::test]
+async fn player_stake_locks_only_withdrawn_tokens() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = env.keypair().await;
+    let mint = vesting_claim(&mut env, &owner, &user, None).await;
+    let badge = badge_pda(&owner.pubkey(), 1);
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+    let escrow = reward_escrow_pda(&user_reward_pda(&user.pubkey(), 1));
+
+    let stake = env.stake_reward_player(&owner.pubkey(), 1, 1, &user.pubkey(), Some(&mint), 0);
+    assert_error(env.send(&[stake], &[&user]).await, BadgeRewardsError::NothingToLock);
+    env.warp(500).await;
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    let stake = env.stake_reward_player(&owner.pubkey(), 1, 1, &user.pubkey(), Some(&mint), 0);
+    env.send(&[withdraw, stake], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&escrow).await, 500);
+
+    let unstake = env.unstake_reward_player(1, &user.pubkey(), Some(&mint));
+    env.send(&[unstake], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 500);
+    assert!(!env.exists(&escrow).await);
+}
+
+#[tokio::test]
+async fn revocation_stops_vesting_and_returns_the_rest() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = vesting_claim(&mut env, &owner, &user, None).await;
+    let badge = badge_pda(&owner.pubkey(), 1);
+    let reward = reward_pda(&owner.pubkey(), 1, 1);
+    let vault = reward_vault_pda(&reward);
+
+    let reclaim = env.reclaim_unvested(&reward, &user.pubkey(), &mint, &badge);
+    assert_error(env.send(&[reclaim], &[]).await, BadgeRewardsError::BadgeNotRevoked);
+
+    env.warp(400).await;
+    let revoke = env.revoke_badge(&owner.pubkey(), 1, &user.pubkey(), false, false);
+    env.send(&[revoke], &[&owner]).await.unwrap();
+    env.warp(300).await;
+    let reclaim = env.reclaim_unvested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[reclaim], &[]).await.unwrap();
+    assert_eq!(env.balance(&vault).await, 4_600);
+    let reclaim = env.reclaim_unvested(&reward, &user.pubkey(), &mint, &badge);
+    assert_error(env.send(&[reclaim], &[]).await, BadgeRewardsError::NothingToReclaim);
+
+    let withdraw = env.withdraw_vested(&reward, &user.pubkey(), &mint, &badge);
+    env.send(&[withdraw], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 400);
+    assert_eq!(env.balance(&vesting_vault_pda(&reward, &user.pubkey())).await, 0);
+}
+
+#[tokio::test]
+async fn close_reward_waits_for_vesting_to_end() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = vesting_claim(&mut env, &owner, &user, None).await;
+
+    let close = env.close_reward(&owner.pubkey(), 1, 1, Some(&mint), true);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::VestingInProgress);
+    env.warp(1_000).await;
+    let close = env.close_reward(&owner.pubkey(), 1, 1, Some(&mint), true);
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 9_000);
+}
\ No newline at end of file
@@ -0,0 +1,207 @@
+use crate::common::*;
+use ::badge_rewards::{Badge, BadgeRewardsError, UserBadge};
+use anchor_lang::{error::ErrorCode, prelude::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::{signature::Keypair, signer::Signer as _};
+
+/// Badge 1 emitting `emission_rate` yield tokens per second from a vault funded with `funding`
+async fn yield_badge(env: &mut Env, owner: &Keypair, emission_rate: u64, funding: u64, fee_bps: Option<u16>) -> Pubkey {
+    let mint = env.create_mint(6, fee_bps).await;
+    env.create_badge(owner, badge_args(1)).await;
+    env.fund_token_account(&owner.pubkey(), &mint, funding).await;
+    let set = env.set_badge_yield(&owner.pubkey(), 1, &mint, emission_rate);
+    let fund = env.fund_badge_yield(&owner.pubkey(), 1, &owner.pubkey(), &mint, funding);
+    env.send(&[set, fund], &[owner]).await.unwrap();
+    mint
+}
+
+#[tokio::test]
+async fn yield_is_shared_between_holders() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let first = Keypair::new();
+    let second = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    env.create_token_account(&first.pubkey(), &mint).await;
+    env.create_token_account(&second.pubkey(), &mint).await;
+
+    env.earn_badge(&owner, 1, &first).await;
+    env.warp(100).await;
+    env.earn_badge(&owner, 1, &second).await;
+    env.warp(100).await;
+
+    let harvest = env.harvest(&owner.pubkey(), 1, &first.pubkey(), &mint);
+    env.send(&[harvest], &[&first]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&first.pubkey(), &mint)).await, 1_500);
+    let harvest = env.harvest(&owner.pubkey(), 1, &second.pubkey(), &mint);
+    env.send(&[harvest], &[&second]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&second.pubkey(), &mint)).await, 500);
+    assert_eq!(env.balance(&yield_vault_pda(&badge_pda(&owner.pubkey(), 1))).await, 8_000);
+
+    let harvest = env.harvest(&owner.pubkey(), 1, &first.pubkey(), &mint);
+    assert_error(env.send(&[harvest], &[&first]).await, BadgeRewardsError::NothingToHarvest);
+}
+
+#[tokio::test]
+async fn set_badge_yield_keeps_its_mint_and_accrues_at_the_old_rate() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    env.warp(100).await;
+    let set = env.set_badge_yield(&owner.pubkey(), 1, &mint, 0);
+    env.send(&[set], &[&owner]).await.unwrap();
+    env.warp(100).await;
+    let harvest = env.harvest(&owner.pubkey(), 1, &user.pubkey(), &mint);
+    env.send(&[harvest], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 1_000);
+
+    let other_mint = env.create_mint(6, None).await;
+    let set = env.set_badge_yield(&owner.pubkey(), 1, &other_mint, 10);
+    assert_error(env.send(&[set], &[&owner]).await, ErrorCode::ConstraintTokenMint);
+    let stranger = Keypair::new();
+    let set = replace_account(env.set_badge_yield(&owner.pubkey(), 1, &mint, 10), &owner.pubkey(), &stranger.pubkey());
+    assert_error(env.send(&[set], &[&stranger]).await, BadgeRewardsError::Unauthorized);
+}
+
+#[tokio::test]
+async fn unstake_pays_unharvested_yield() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    env.warp(100).await;
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &user.pubkey(), false, Some(&mint));
+    env.send(&[unstake], &[&owner, &user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 1_000);
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(badge.total_earned, 0);
+}
+
+#[tokio::test]
+async fn unstake_is_not_blocked_by_an_underfunded_vault_or_missing_yield_accounts() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let first = Keypair::new();
+    let second = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 600, None).await;
+    let yield_vault = yield_vault_pda(&badge_pda(&owner.pubkey(), 1));
+    env.create_token_account(&second.pubkey(), &mint).await;
+    env.earn_badge(&owner, 1, &first).await;
+    env.earn_badge(&owner, 1, &second).await;
+    env.warp(200).await;
+
+    // Without the yield accounts the first holder leaves and forfeits their 1_000
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &first.pubkey(), false, None);
+    env.send(&[unstake], &[&owner, &first]).await.unwrap();
+    assert_eq!(env.balance(&yield_vault).await, 600);
+
+    // The second is owed 1_000 but the vault only holds 600
+    let unstake = env.unstake_badge(&owner.pubkey(), 1, &second.pubkey(), false, Some(&mint));
+    env.send(&[unstake], &[&owner, &second]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&second.pubkey(), &mint)).await, 600);
+    assert_eq!(env.balance(&yield_vault).await, 0);
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(badge.reserved_yield, 0);
+}
+
+#[tokio::test]
+async fn revoke_forfeits_unharvested_yield() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    env.warp(100).await;
+    let revoke = env.revoke_badge(&owner.pubkey(), 1, &user.pubkey(), false, false);
+    env.send(&[revoke], &[&owner]).await.unwrap();
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(badge.reserved_yield, 0);
+    let withdraw = env.withdraw_badge_yield(&owner.pubkey(), 1, &mint, 10_000);
+    env.send(&[withdraw], &[&owner]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 10_000);
+}
+
+#[tokio::test]
+async fn withdraw_badge_yield_keeps_the_yield_accrued_to_holders() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    env.create_token_account(&user.pubkey(), &mint).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    env.warp(100).await;
+    let withdraw = env.withdraw_badge_yield(&owner.pubkey(), 1, &mint, 9_001);
+    assert_error(env.send(&[withdraw], &[&owner]).await, BadgeRewardsError::InsufficientVaultBalance);
+    let withdraw = env.withdraw_badge_yield(&owner.pubkey(), 1, &mint, 9_000);
+    env.send(&[withdraw], &[&owner]).await.unwrap();
+
+    let harvest = env.harvest(&owner.pubkey(), 1, &user.pubkey(), &mint);
+    env.send(&[harvest], &[&user]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&user.pubkey(), &mint)).await, 1_000);
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    assert_eq!(badge.reserved_yield, 0);
+}
+
+#[tokio::test]
+async fn new_holder_starts_without_past_yield() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let user = Keypair::new();
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    env.warp(100).await;
+    env.earn_badge(&owner, 1, &user).await;
+
+    let badge: Badge = env.state(&badge_pda(&owner.pubkey(), 1)).await;
+    let user_badge: UserBadge = env.state(&user_badge_pda(&user.pubkey(), 1)).await;
+    assert_eq!(user_badge.yield_debt, badge.acc_yield_per_badge);
+    env.create_token_account(&user.pubkey(), &mint).await;
+    let harvest = env.harvest(&owner.pubkey(), 1, &user.pubkey(), &mint);
+    assert_error(env.send(&[harvest], &[&user]).await, BadgeRewardsError::NothingToHarvest);
+}
+
+#[tokio::test]
+async fn withdraw_badge_yield_and_close_badge_with_its_vault() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, None).await;
+    let badge = badge_pda(&owner.pubkey(), 1);
+
+    let close = env.close_badge(&owner.pubkey(), 1, Some(&mint));
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::YieldVaultNotEmpty);
+    let withdraw = env.withdraw_badge_yield(&owner.pubkey(), 1, &mint, 10_001);
+    assert_error(env.send(&[withdraw], &[&owner]).await, BadgeRewardsError::InsufficientVaultBalance);
+    let withdraw = env.withdraw_badge_yield(&owner.pubkey(), 1, &mint, 10_000);
+    env.send(&[withdraw], &[&owner]).await.unwrap();
+    assert_eq!(env.balance(&token_account(&owner.pubkey(), &mint)).await, 10_000);
+
+    let close = env.close_badge(&owner.pubkey(), 1, None);
+    assert_error(env.send(&[close], &[&owner]).await, BadgeRewardsError::MissingYieldAccounts);
+    let close = env.close_badge(&owner.pubkey(), 1, Some(&mint));
+    env.send(&[close], &[&owner]).await.unwrap();
+    assert!(!env.exists(&badge).await);
+    assert!(!env.exists(&yield_vault_pda(&badge)).await);
+}
+
+#[tokio::test]
+async fn close_badge_harvests_withheld_fees_from_the_yield_vault() {
+    let mut env = Env::new().await;
+    let owner = env.keypair().await;
+    let mint = yield_badge(&mut env, &owner, 10, 10_000, Some(100)).await;
+    let yield_vault = yield_vault_pda(&badge_pda(&owner.pubkey(), 1));
+    assert_eq!(env.balance(&yield_vault).await, 9_900);
+    assert_eq!(env.withheld(&yield_vault).await, 100);
+
+    let withdraw = env.withdraw_badge_yield(&owner.pubkey(), 1, &mint, 9_900);
+    let close = env.close_badge(&owner.pubkey(), 1, Some(&mint));
+    env.send(&[withdraw, close], &[&owner]).await.unwrap();
+    assert!(!env.exists(&yield_vault).await);
+}
\ No newline at end of file